@@ -1,10 +1,10 @@
 mod utils;
 
-use proc_macro_error::{abort_call_site, proc_macro_error};
-use quote::{format_ident, quote};
-use syn::{punctuated::Punctuated, token::Comma, Data, DeriveInput, Field};
+use proc_macro_error::{abort, abort_call_site, proc_macro_error};
+use quote::{format_ident, quote, ToTokens};
+use syn::{punctuated::Punctuated, token::Comma, Data, DeriveInput, Field, Meta, NestedMeta, Path};
 
-#[proc_macro_derive(Services)]
+#[proc_macro_derive(Services, attributes(overwatch))]
 #[proc_macro_error]
 pub fn derive_services(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input: DeriveInput = syn::parse(input).expect("A syn parseable token stream");
@@ -47,6 +47,7 @@ fn impl_services_for_struct(
     let settings = generate_services_settings(identifier, fields);
     let unique_ids_check = generate_assert_unique_identifiers(identifier, fields);
     let services_impl = generate_services_impl(identifier, fields);
+    let relay_accessors = generate_relay_accessors(identifier, fields);
 
     quote! {
         #unique_ids_check
@@ -54,6 +55,82 @@ fn impl_services_for_struct(
         #settings
 
         #services_impl
+
+        #relay_accessors
+    }
+}
+
+/// Services declared through `#[overwatch(relays_to(ServiceB, ...))]` on a field
+fn relays_to_targets(field: &Field) -> Vec<Path> {
+    let mut targets = Vec::new();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("overwatch"))
+    {
+        let nested = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested,
+            _ => abort!(attr, "Expected #[overwatch(relays_to(...))]"),
+        };
+        for meta in nested {
+            match meta {
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("relays_to") => {
+                    for target in list.nested {
+                        match target {
+                            NestedMeta::Meta(Meta::Path(path)) => targets.push(path),
+                            other => abort!(other, "Expected a service type"),
+                        }
+                    }
+                }
+                other => abort!(
+                    other,
+                    "Unknown overwatch attribute, expected relays_to(...)"
+                ),
+            }
+        }
+    }
+    targets
+}
+
+fn generate_relay_accessors(
+    services_identifier: &proc_macro2::Ident,
+    fields: &Punctuated<Field, Comma>,
+) -> proc_macro2::TokenStream {
+    let accessors = fields.iter().flat_map(|field| {
+        let field_identifier = field.ident.as_ref().expect("A struct attribute identifier");
+        relays_to_targets(field).into_iter().map(move |target| {
+            let target_type = target.to_token_stream().to_string();
+            let target_field = fields
+                .iter()
+                .find(|field| utils::path_names_type(&target, &utils::extract_type_from(&field.ty)))
+                .unwrap_or_else(|| {
+                    abort!(
+                        target,
+                        "Service {} is not part of {}",
+                        target_type,
+                        services_identifier
+                    )
+                });
+            let target_identifier = target_field
+                .ident
+                .as_ref()
+                .expect("A struct attribute identifier");
+            let accessor_identifier =
+                format_ident!("relay_{}_to_{}", field_identifier, target_identifier);
+            quote! {
+                pub fn #accessor_identifier(
+                    overwatch_handle: &::overwatch::overwatch::handle::OverwatchHandle,
+                ) -> ::overwatch::services::relay::Relay<#target> {
+                    overwatch_handle.relay::<#target>()
+                }
+            }
+        })
+    });
+
+    quote! {
+        impl #services_identifier {
+            #( #accessors )*
+        }
     }
 }
 
@@ -104,9 +181,15 @@ fn generate_services_impl(
     let impl_new = generate_new_impl(fields);
     let impl_start_all = generate_start_all_impl(fields);
     let impl_start = generate_start_impl(fields);
+    let impl_service_ids = generate_service_ids_impl(fields);
+    let impl_is_critical = generate_is_critical_impl(fields);
     let impl_stop = generate_stop_impl(fields);
     let impl_relay = generate_request_relay_impl(fields);
+    let impl_state_watcher = generate_request_state_watcher_impl(fields);
+    let impl_throughput = generate_request_throughput_impl(fields);
+    let impl_abort_handle = generate_request_abort_handle_impl(fields);
     let impl_update_settings = generate_update_settings_impl(fields);
+    let impl_settings = generate_settings_impl(fields);
 
     quote! {
         impl ::overwatch::overwatch::Services for #services_identifier {
@@ -118,11 +201,23 @@ fn generate_services_impl(
 
             #impl_start
 
+            #impl_service_ids
+
+            #impl_is_critical
+
             #impl_stop
 
             #impl_relay
 
+            #impl_state_watcher
+
+            #impl_throughput
+
+            #impl_abort_handle
+
             #impl_update_settings
+
+            #impl_settings
         }
     }
 }
@@ -167,6 +262,16 @@ fn generate_new_impl(fields: &Punctuated<Field, Comma>) -> proc_macro2::TokenStr
 }
 
 fn generate_start_all_impl(fields: &Punctuated<Field, Comma>) -> proc_macro2::TokenStream {
+    let call_check = fields.iter().map(|field| {
+        let field_identifier = field.ident.as_ref().expect("A struct attribute identifier");
+        quote! {
+            self.#field_identifier
+                .check_relay_buffer_size()
+                .map_err(::overwatch::services::ServiceError::from)?;
+            self.#field_identifier.check_initial_settings()?;
+        }
+    });
+
     let call_start = fields.iter().map(|field| {
         let field_identifier = field.ident.as_ref().expect("A struct attribute identifier");
         quote! {
@@ -177,6 +282,8 @@ fn generate_start_all_impl(fields: &Punctuated<Field, Comma>) -> proc_macro2::To
     quote! {
         #[::tracing::instrument(skip(self), err)]
         fn start_all(&mut self) -> Result<(), ::overwatch::overwatch::Error> {
+            // no service is started if any of them is misconfigured
+            #( #call_check )*
             #( #call_start )*
             Ok(())
         }
@@ -189,6 +296,10 @@ fn generate_start_impl(fields: &Punctuated<Field, Comma>) -> proc_macro2::TokenS
         let type_id = utils::extract_type_from(&field.ty);
         quote! {
             <#type_id as ::overwatch::services::ServiceData>::SERVICE_ID => {
+                self.#field_identifier
+                    .check_relay_buffer_size()
+                    .map_err(::overwatch::services::ServiceError::from)?;
+                self.#field_identifier.check_initial_settings()?;
                 self.#field_identifier.service_runner().run();
                 Ok(())
             }
@@ -206,6 +317,41 @@ fn generate_start_impl(fields: &Punctuated<Field, Comma>) -> proc_macro2::TokenS
     }
 }
 
+fn generate_service_ids_impl(fields: &Punctuated<Field, Comma>) -> proc_macro2::TokenStream {
+    let services_ids = fields.iter().map(|field| {
+        let type_id = utils::extract_type_from(&field.ty);
+        quote! {
+            <#type_id as ::overwatch::services::ServiceData>::SERVICE_ID
+        }
+    });
+
+    quote! {
+        fn service_ids(&self) -> ::std::vec::Vec<::overwatch::services::ServiceId> {
+            ::std::vec![#( #services_ids ),*]
+        }
+    }
+}
+
+fn generate_is_critical_impl(fields: &Punctuated<Field, Comma>) -> proc_macro2::TokenStream {
+    let cases = fields.iter().map(|field| {
+        let type_id = utils::extract_type_from(&field.ty);
+        quote! {
+            <#type_id as ::overwatch::services::ServiceData>::SERVICE_ID => {
+                <#type_id as ::overwatch::services::ServiceData>::CRITICAL
+            }
+        }
+    });
+
+    quote! {
+        fn is_critical(&self, service_id: ::overwatch::services::ServiceId) -> bool {
+            match service_id {
+                #( #cases )*
+                _ => true
+            }
+        }
+    }
+}
+
 fn generate_stop_impl(fields: &Punctuated<Field, Comma>) -> proc_macro2::TokenStream {
     let cases = fields.iter().map(|field| {
         let _field_identifier = field.ident.as_ref().expect("A struct attribute identifier");
@@ -255,6 +401,75 @@ fn generate_request_relay_impl(fields: &Punctuated<Field, Comma>) -> proc_macro2
     }
 }
 
+fn generate_request_state_watcher_impl(
+    fields: &Punctuated<Field, Comma>,
+) -> proc_macro2::TokenStream {
+    let cases = fields.iter().map(|field| {
+        let field_identifier = field.ident.as_ref().expect("A struct attribute identifier");
+        let type_id = utils::extract_type_from(&field.ty);
+        quote! {
+            <#type_id as ::overwatch::services::ServiceData>::SERVICE_ID => {
+                ::std::option::Option::Some(::std::boxed::Box::new(
+                    self.#field_identifier.state_watcher()
+                ) as ::overwatch::services::state::AnyStateWatcher)
+            }
+        }
+    });
+
+    quote! {
+        fn request_state_watcher(&self, service_id: ::overwatch::services::ServiceId) -> ::std::option::Option<::overwatch::services::state::AnyStateWatcher> {
+            match service_id {
+                #( #cases )*
+                _ => ::std::option::Option::None
+            }
+        }
+    }
+}
+
+fn generate_request_throughput_impl(fields: &Punctuated<Field, Comma>) -> proc_macro2::TokenStream {
+    let cases = fields.iter().map(|field| {
+        let field_identifier = field.ident.as_ref().expect("A struct attribute identifier");
+        let type_id = utils::extract_type_from(&field.ty);
+        quote! {
+            <#type_id as ::overwatch::services::ServiceData>::SERVICE_ID => {
+                self.#field_identifier.throughput()
+            }
+        }
+    });
+
+    quote! {
+        fn request_throughput(&self, service_id: ::overwatch::services::ServiceId) -> ::std::option::Option<f64> {
+            match service_id {
+                #( #cases )*
+                _ => ::std::option::Option::None
+            }
+        }
+    }
+}
+
+fn generate_request_abort_handle_impl(
+    fields: &Punctuated<Field, Comma>,
+) -> proc_macro2::TokenStream {
+    let cases = fields.iter().map(|field| {
+        let field_identifier = field.ident.as_ref().expect("A struct attribute identifier");
+        let type_id = utils::extract_type_from(&field.ty);
+        quote! {
+            <#type_id as ::overwatch::services::ServiceData>::SERVICE_ID => {
+                self.#field_identifier.abort_handle()
+            }
+        }
+    });
+
+    quote! {
+        fn request_abort_handle(&mut self, service_id: ::overwatch::services::ServiceId) -> ::std::option::Option<::overwatch::services::handle::AbortHandle> {
+            match service_id {
+                #( #cases )*
+                _ => ::std::option::Option::None
+            }
+        }
+    }
+}
+
 fn generate_update_settings_impl(fields: &Punctuated<Field, Comma>) -> proc_macro2::TokenStream {
     let fields_settings = fields.iter().map(|field| {
         let field_identifier = field.ident.as_ref().expect("A struct attribute identifier");
@@ -264,11 +479,29 @@ fn generate_update_settings_impl(fields: &Punctuated<Field, Comma>) -> proc_macr
         }
     });
 
-    let update_settings_call = fields.iter().map(|field| {
+    let prepare_settings_call = fields.iter().map(|field| {
+        let field_identifier = field.ident.as_ref().expect("A struct attribute identifier");
+        let service_type = utils::extract_type_from(&field.ty);
+        let settings_field_identifier = service_settings_field_identifier_from(field_identifier);
+        quote! {
+            let #settings_field_identifier =
+                match self.#field_identifier.prepare_settings(#settings_field_identifier) {
+                    ::std::result::Result::Ok(settings) => ::std::option::Option::Some(settings),
+                    ::std::result::Result::Err(e) => {
+                        failures.push((<#service_type as ::overwatch::services::ServiceData>::SERVICE_ID, e));
+                        ::std::option::Option::None
+                    }
+                };
+        }
+    });
+
+    let publish_settings_call = fields.iter().map(|field| {
         let field_identifier = field.ident.as_ref().expect("A struct attribute identifier");
         let settings_field_identifier = service_settings_field_identifier_from(field_identifier);
         quote! {
-            self.#field_identifier.update_settings(#settings_field_identifier);
+            if let ::std::option::Option::Some(settings) = #settings_field_identifier {
+                self.#field_identifier.publish_settings(settings);
+            }
         }
     });
 
@@ -279,9 +512,35 @@ fn generate_update_settings_impl(fields: &Punctuated<Field, Comma>) -> proc_macr
                 #( #fields_settings ),*
             } = settings;
 
-            #( #update_settings_call )*
+            // validate every service settings before publishing any of them
+            let mut failures = ::std::vec::Vec::new();
+            #( #prepare_settings_call )*
+            if !failures.is_empty() {
+                return ::std::result::Result::Err(
+                    ::overwatch::overwatch::Error::SettingsRejected { failures }
+                );
+            }
+
+            #( #publish_settings_call )*
 
             Ok(())
         }
     }
 }
+
+fn generate_settings_impl(fields: &Punctuated<Field, Comma>) -> proc_macro2::TokenStream {
+    let fields_settings = fields.iter().map(|field| {
+        let field_identifier = field.ident.as_ref().expect("A struct attribute identifier");
+        quote! {
+            #field_identifier: self.#field_identifier.settings()
+        }
+    });
+
+    quote! {
+        fn settings(&self) -> Self::Settings {
+            Self::Settings {
+                #( #fields_settings ),*
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use proc_macro_error::abort_call_site;
 use quote::ToTokens;
-use syn::{GenericArgument, PathArguments, Type};
+use syn::{GenericArgument, Path, PathArguments, Type};
 
 pub fn extract_type_from(ty: &Type) -> Type {
     let stringify_type = ty.clone().into_token_stream().to_string();
@@ -32,3 +32,21 @@ pub fn extract_type_from(ty: &Type) -> Type {
         _ => abort_call_site!("Expected single type argument, found {}", stringify_type),
     }
 }
+
+/// Whether `path` names the `ty` type
+/// Only the last segment and its generic arguments are compared, so `services::Foo` matches a
+/// `Foo` imported from anywhere.
+pub fn path_names_type(path: &Path, ty: &Type) -> bool {
+    let ty_path = match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => &type_path.path,
+        _ => return false,
+    };
+    match (path.segments.last(), ty_path.segments.last()) {
+        (Some(target), Some(segment)) => {
+            target.ident == segment.ident
+                && target.arguments.to_token_stream().to_string()
+                    == segment.arguments.to_token_stream().to_string()
+        }
+        _ => false,
+    }
+}
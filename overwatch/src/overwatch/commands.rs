@@ -1,11 +1,13 @@
 // std
 
 // crates
-use crate::overwatch::AnySettings;
+use crate::overwatch::{AnySettings, Error};
+use futures::future::AbortHandle;
 use tokio::sync::oneshot;
 
 // internal
 use crate::services::relay::RelayResult;
+use crate::services::state::AnyStateWatcher;
 use crate::services::ServiceId;
 
 #[derive(Debug)]
@@ -30,6 +32,33 @@ pub struct RelayCommand {
     pub(crate) reply_channel: ReplyChannel<RelayResult>,
 }
 
+/// Command for requesting the raw [`AbortHandle`] of a running service
+#[derive(Debug)]
+pub struct AbortHandleCommand {
+    pub(crate) service_id: ServiceId,
+    pub(crate) reply_channel: ReplyChannel<Option<AbortHandle>>,
+}
+
+/// Command for requesting a watcher of a service state
+#[derive(Debug)]
+pub struct StateWatcherCommand {
+    pub(crate) service_id: ServiceId,
+    pub(crate) reply_channel: ReplyChannel<Option<AnyStateWatcher>>,
+}
+
+/// Command for requesting the throughput of a service
+#[derive(Debug)]
+pub struct ThroughputCommand {
+    pub(crate) service_id: ServiceId,
+    pub(crate) reply_channel: ReplyChannel<Option<f64>>,
+}
+
+/// Command for requesting the current settings of every service
+#[derive(Debug)]
+pub struct ExportConfigCommand {
+    pub(crate) reply_channel: ReplyChannel<AnySettings>,
+}
+
 /// Command for managing [`ServiceCore`](crate::services::ServiceCore) lifecycle
 #[allow(unused)]
 #[derive(Debug)]
@@ -56,13 +85,28 @@ pub enum OverwatchLifeCycleCommand {
 
 /// [`Overwatch`](crate::overwatch::Overwatch) settings update command
 #[derive(Debug)]
-pub struct SettingsCommand(pub(crate) AnySettings);
+pub struct SettingsCommand {
+    pub(crate) settings: AnySettings,
+    pub(crate) reply_channel: ReplyChannel<Result<(), Error>>,
+}
+
+/// Command for starting a service left out of the app startup
+#[derive(Debug)]
+pub struct StartCommand {
+    pub(crate) service_id: ServiceId,
+    pub(crate) reply_channel: ReplyChannel<Result<(), Error>>,
+}
 
 /// [`Overwatch`](crate::overwatch::Overwatch) tasks related commands
 #[derive(Debug)]
 pub enum OverwatchCommand {
     Relay(RelayCommand),
+    AbortHandle(AbortHandleCommand),
+    StateWatcher(StateWatcherCommand),
+    Throughput(ThroughputCommand),
     ServiceLifeCycle(ServiceLifeCycleCommand),
     OverwatchLifeCycle(OverwatchLifeCycleCommand),
     Settings(SettingsCommand),
+    ExportConfig(ExportConfigCommand),
+    Start(StartCommand),
 }
@@ -0,0 +1,168 @@
+// std
+use std::fmt::Debug;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+// crates
+use async_trait::async_trait;
+use thiserror::Error;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+// internal
+use crate::overwatch::resources::ManagedResource;
+
+/// Creates and validates the connections of a [`ConnectionPool`]
+#[async_trait]
+pub trait ConnectionManager: Send + Sync + 'static {
+    type Connection: Send + 'static;
+    type Error: Debug + Send;
+    /// Open a new connection
+    async fn connect(&self) -> Result<Self::Connection, Self::Error>;
+    /// Whether an idle connection can still be used, it is checked on every checkout
+    async fn is_valid(&self, connection: &mut Self::Connection) -> bool;
+}
+
+#[derive(Error, Debug)]
+pub enum PoolError<E: Debug> {
+    #[error("connection pool is closed")]
+    Closed,
+    #[error("error opening a connection: {0:?}")]
+    Connect(E),
+}
+
+/// Connection pool configuration
+#[derive(Clone, Copy, Debug)]
+pub struct PoolSettings {
+    /// Maximum amount of connections checked out at once, further checkouts wait for a free one
+    pub max_size: usize,
+    /// Idle connections older than this are closed instead of being reused
+    pub max_idle_time: Duration,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        Self {
+            max_size: 10,
+            max_idle_time: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Health checked pool of connections shared by the services of an overwatch application
+/// Register it with [`crate::overwatch::OverwatchRunnerBuilder::managed_resource`] so services
+/// check connections out of the same pool and it is closed once the app shuts down. Returned
+/// connections are kept idle for reuse, expired or invalid ones are closed on checkout.
+pub struct ConnectionPool<M: ConnectionManager> {
+    manager: M,
+    settings: PoolSettings,
+    idle: Mutex<Vec<(M::Connection, Instant)>>,
+    slots: Arc<Semaphore>,
+    closed: AtomicBool,
+}
+
+/// Connection checked out of a [`ConnectionPool`], it goes back to the pool when dropped
+pub struct PooledConnection<'pool, M: ConnectionManager> {
+    pool: &'pool ConnectionPool<M>,
+    connection: Option<M::Connection>,
+    _slot: OwnedSemaphorePermit,
+}
+
+impl<M: ConnectionManager> ConnectionPool<M> {
+    pub fn new(manager: M, settings: PoolSettings) -> Self {
+        Self {
+            manager,
+            settings,
+            idle: Mutex::new(Vec::new()),
+            slots: Arc::new(Semaphore::new(settings.max_size)),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    /// Check a connection out of the pool
+    /// Reuses the most recently returned valid connection, opening a new one if there is none.
+    /// Waits while `max_size` connections are checked out.
+    pub async fn checkout(&self) -> Result<PooledConnection<'_, M>, PoolError<M::Error>> {
+        let slot = self
+            .slots
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| PoolError::Closed)?;
+        while let Some(mut connection) = self.pop_idle() {
+            if self.manager.is_valid(&mut connection).await {
+                return Ok(self.pooled(connection, slot));
+            }
+        }
+        let connection = self.manager.connect().await.map_err(PoolError::Connect)?;
+        Ok(self.pooled(connection, slot))
+    }
+
+    /// Amount of idle connections ready to be reused
+    pub fn idle_connections(&self) -> usize {
+        self.idle.lock().unwrap().len()
+    }
+
+    /// Close the pool, dropping its idle connections
+    /// Pending and later checkouts fail, connections still checked out are dropped once returned.
+    pub fn close(&self) {
+        {
+            let mut idle = self.idle.lock().unwrap();
+            self.closed.store(true, Ordering::SeqCst);
+            idle.clear();
+        }
+        self.slots.close();
+    }
+
+    fn pop_idle(&self) -> Option<M::Connection> {
+        let mut idle = self.idle.lock().unwrap();
+        while let Some((connection, returned_at)) = idle.pop() {
+            if returned_at.elapsed() <= self.settings.max_idle_time {
+                return Some(connection);
+            }
+        }
+        None
+    }
+
+    fn pooled(
+        &self,
+        connection: M::Connection,
+        slot: OwnedSemaphorePermit,
+    ) -> PooledConnection<'_, M> {
+        PooledConnection {
+            pool: self,
+            connection: Some(connection),
+            _slot: slot,
+        }
+    }
+}
+
+impl<M: ConnectionManager> ManagedResource for ConnectionPool<M> {
+    fn shutdown(&self) {
+        self.close();
+    }
+}
+
+impl<M: ConnectionManager> Deref for PooledConnection<'_, M> {
+    type Target = M::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        self.connection.as_ref().expect("Connection until dropped")
+    }
+}
+
+impl<M: ConnectionManager> DerefMut for PooledConnection<'_, M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.connection.as_mut().expect("Connection until dropped")
+    }
+}
+
+impl<M: ConnectionManager> Drop for PooledConnection<'_, M> {
+    fn drop(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            let mut idle = self.pool.idle.lock().unwrap();
+            if !self.pool.closed.load(Ordering::SeqCst) {
+                idle.push((connection, Instant::now()));
+            }
+        }
+    }
+}
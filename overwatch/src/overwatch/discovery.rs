@@ -0,0 +1,20 @@
+// std
+// crates
+// internal
+use crate::services::ServiceId;
+
+/// Extension point to publish the overwatch services into an external registry
+/// Overwatch registers every service once it is running and deregisters them, in reverse startup
+/// order, when the runner finishes. Services are in-process, so only their identifiers are
+/// published.
+pub trait ServiceDiscovery: Send + Sync + 'static {
+    fn register(&self, _service_id: ServiceId) {}
+
+    fn deregister(&self, _service_id: ServiceId) {}
+}
+
+/// [`ServiceDiscovery`] that does not publish services anywhere
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoDiscovery;
+
+impl ServiceDiscovery for NoDiscovery {}
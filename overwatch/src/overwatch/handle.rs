@@ -1,15 +1,32 @@
 // std
-
+#[cfg(any(feature = "chaos", feature = "testing"))]
+use std::collections::HashMap;
+use std::sync::Arc;
 // crates
-use crate::overwatch::commands::{OverwatchCommand, OverwatchLifeCycleCommand, SettingsCommand};
-use crate::overwatch::Services;
+use crate::overwatch::commands::{
+    AbortHandleCommand, ExportConfigCommand, OverwatchCommand, OverwatchLifeCycleCommand,
+    ReplyChannel, SettingsCommand, StartCommand, StateWatcherCommand, ThroughputCommand,
+};
+use crate::overwatch::pool::WorkerPool;
+use crate::overwatch::resources::Resources;
+use crate::overwatch::{Error, Services, SpawnHook};
+use futures::future::AbortHandle;
+use futures::{Stream, StreamExt};
 use tokio::runtime::Handle;
 use tokio::sync::mpsc::Sender;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 use tracing::{error, info, instrument};
 
 // internal
-use crate::services::relay::Relay;
-use crate::services::ServiceCore;
+#[cfg(feature = "chaos")]
+use crate::services::chaos::FaultInjector;
+#[cfg(feature = "testing")]
+use crate::services::record::{AnyRecorder, MessageRecorder};
+use crate::services::relay::{BufferBudget, OutboundRelay, Relay, RelayBufferLimits, RelayError};
+use crate::services::state::{StateSnapshot, StateWatcher};
+use crate::services::{ServiceCore, ServiceId};
+use crate::utils::clock::{Clock, SystemClock};
 
 /// Handler object over the main Overwatch runner
 /// It handles communications to the main Overwatch runner.
@@ -18,6 +35,16 @@ pub struct OverwatchHandle {
     #[allow(unused)]
     runtime_handle: Handle,
     sender: Sender<OverwatchCommand>,
+    resources: Resources,
+    worker_pool: Option<WorkerPool>,
+    buffer_budget: Arc<BufferBudget>,
+    relay_buffer_limits: RelayBufferLimits,
+    clock: Arc<dyn Clock>,
+    spawn_hook: Option<SpawnHook>,
+    #[cfg(feature = "chaos")]
+    faults: Arc<HashMap<ServiceId, FaultInjector>>,
+    #[cfg(feature = "testing")]
+    recorders: Arc<HashMap<ServiceId, AnyRecorder>>,
 }
 
 impl OverwatchHandle {
@@ -25,15 +52,219 @@ impl OverwatchHandle {
         Self {
             runtime_handle,
             sender,
+            resources: Resources::default(),
+            worker_pool: None,
+            buffer_budget: Arc::new(BufferBudget::default()),
+            relay_buffer_limits: RelayBufferLimits::default(),
+            clock: Arc::new(SystemClock),
+            spawn_hook: None,
+            #[cfg(feature = "chaos")]
+            faults: Arc::new(HashMap::new()),
+            #[cfg(feature = "testing")]
+            recorders: Arc::new(HashMap::new()),
         }
     }
 
+    /// Share the provided resources registry with every holder of this handle
+    pub fn with_resources(mut self, resources: Resources) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    /// App-wide resources registry
+    pub(crate) fn resources(&self) -> &Resources {
+        &self.resources
+    }
+
+    /// Share the provided worker pool with every holder of this handle
+    pub fn with_worker_pool(mut self, worker_pool: WorkerPool) -> Self {
+        self.worker_pool = Some(worker_pool);
+        self
+    }
+
+    /// App-wide worker pool, if one was configured
+    pub fn worker_pool(&self) -> Option<&WorkerPool> {
+        self.worker_pool.as_ref()
+    }
+
+    /// Account every service relay buffer in the provided budget
+    pub fn with_buffer_budget(mut self, buffer_budget: Arc<BufferBudget>) -> Self {
+        self.buffer_budget = buffer_budget;
+        self
+    }
+
+    /// App-wide budget the service relays buffers are accounted in
+    pub fn buffer_budget(&self) -> Arc<BufferBudget> {
+        self.buffer_budget.clone()
+    }
+
+    /// Check every service relay buffer size against the provided limits
+    pub fn with_relay_buffer_limits(mut self, relay_buffer_limits: RelayBufferLimits) -> Self {
+        self.relay_buffer_limits = relay_buffer_limits;
+        self
+    }
+
+    /// Limits the service relays buffer sizes are checked against
+    pub fn relay_buffer_limits(&self) -> RelayBufferLimits {
+        self.relay_buffer_limits
+    }
+
+    /// Approximate bytes currently buffered across every service relay
+    pub fn buffered_bytes(&self) -> usize {
+        self.buffer_budget.buffered_bytes()
+    }
+
+    /// Read time in every holder of this handle from the provided clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// App-wide clock services read time from
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Wrap every service main loop spawned through this handle with the provided hook
+    pub fn with_spawn_hook(mut self, spawn_hook: SpawnHook) -> Self {
+        self.spawn_hook = Some(spawn_hook);
+        self
+    }
+
+    /// Hook wrapping the services main loops, if one was configured
+    pub fn spawn_hook(&self) -> Option<&SpawnHook> {
+        self.spawn_hook.as_ref()
+    }
+
+    /// Inject the provided faults on the relays of the matching services
+    #[cfg(feature = "chaos")]
+    pub fn with_faults(mut self, faults: HashMap<ServiceId, FaultInjector>) -> Self {
+        self.faults = Arc::new(faults);
+        self
+    }
+
+    /// Faults injected on the relay of a service, if any were configured for it
+    /// Update them through the returned injector to tune the faults at runtime.
+    #[cfg(feature = "chaos")]
+    pub fn fault_injector(&self, service_id: ServiceId) -> Option<FaultInjector> {
+        self.faults.get(service_id).cloned()
+    }
+
+    /// Record the messages delivered to the matching services with the provided recorders
+    #[cfg(feature = "testing")]
+    pub fn with_recorders(mut self, recorders: HashMap<ServiceId, AnyRecorder>) -> Self {
+        self.recorders = Arc::new(recorders);
+        self
+    }
+
+    /// Recorder of the messages delivered to a service, if recording was configured for it
+    #[cfg(feature = "testing")]
+    pub fn message_recorder<M: 'static>(
+        &self,
+        service_id: ServiceId,
+    ) -> Option<MessageRecorder<M>> {
+        self.recorders
+            .get(service_id)
+            .and_then(|recorder| recorder.downcast_ref::<MessageRecorder<M>>())
+            .cloned()
+    }
+
+    /// Get the app-wide shared resource of type `T`
+    /// Returns `None` if no resource of that type was registered
+    pub fn resource<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.resources.get::<T>()
+    }
+
     /// Request for a relay to an specific service by type
+    /// To reach the service from another overwatch application see
+    /// [`crate::services::bridge::BridgeRelay`].
     pub fn relay<S: ServiceCore>(&self) -> Relay<S> {
         Relay::new(self.clone())
     }
 
+    /// Request `n` independent relays to an specific service by type
+    /// Every relay is a sender of the same bounded `tokio::sync::mpsc` channel: they are cheap to
+    /// hold and send concurrently without locking each other, but they share the service buffer
+    /// of [`crate::services::ServiceData::SERVICE_RELAY_BUFFER_SIZE`] messages, so a fast producer
+    /// can fill it for everyone. Messages from a single relay keep their order, there is no
+    /// ordering between different relays.
+    pub async fn relay_many<S: ServiceCore>(
+        &self,
+        n: usize,
+    ) -> Result<Vec<OutboundRelay<S::Message>>, RelayError> {
+        let relay = self.relay::<S>().connect().await?;
+        Ok(vec![relay; n])
+    }
+
+    /// Request the raw [`AbortHandle`] of a running service by type
+    /// Aborting the service through it bypasses any graceful shutdown, it is meant as an escape
+    /// hatch for custom supervisors. Returns `None` if the service is not running.
+    pub async fn abort_handle<S: ServiceCore>(&mut self) -> Option<AbortHandle> {
+        let (reply, receiver) = oneshot::channel();
+        self.send(OverwatchCommand::AbortHandle(AbortHandleCommand {
+            service_id: S::SERVICE_ID,
+            reply_channel: ReplyChannel(reply),
+        }))
+        .await;
+        receiver.await.unwrap_or_else(|e| {
+            error!(error=?e, "Error receiving abort handle for service {}", S::SERVICE_ID);
+            None
+        })
+    }
+
+    /// Request a watcher of an specific service state by type
+    /// The watcher is seeded with the current state, so it can be read right away.
+    /// Returns `None` if the service is not part of the overwatch application.
+    pub async fn state_watcher<S: ServiceCore>(&mut self) -> Option<StateWatcher<S::State>> {
+        let (reply, receiver) = oneshot::channel();
+        self.send(OverwatchCommand::StateWatcher(StateWatcherCommand {
+            service_id: S::SERVICE_ID,
+            reply_channel: ReplyChannel(reply),
+        }))
+        .await;
+        let watcher = receiver.await.unwrap_or_else(|e| {
+            error!(error=?e, "Error receiving state watcher for service {}", S::SERVICE_ID);
+            None
+        })?;
+        match watcher.downcast::<StateWatcher<S::State>>() {
+            Ok(watcher) => Some(*watcher),
+            Err(_) => {
+                error!("Invalid state watcher type for service {}", S::SERVICE_ID);
+                None
+            }
+        }
+    }
+
+    /// Take a timestamped copy of an specific service state by type
+    /// Snapshots are timestamped with the overwatch clock, compare them with
+    /// [`crate::services::state::diff`].
+    /// Returns `None` if the service is not part of the overwatch application.
+    pub async fn state_snapshot<S: ServiceCore>(&mut self) -> Option<StateSnapshot<S::State>> {
+        let watcher = self.state_watcher::<S>().await?;
+        Some(StateSnapshot {
+            taken_at: self.clock.system_time(),
+            state: watcher.state_cloned(),
+        })
+    }
+
+    /// Request the moving average of messages per second sent to an specific service by type
+    /// Services not started yet count the messages buffered for them. Returns `None` if the
+    /// service is not part of the app or the runner is unavailable.
+    pub async fn throughput<S: ServiceCore>(&mut self) -> Option<f64> {
+        let (reply, receiver) = oneshot::channel();
+        self.send(OverwatchCommand::Throughput(ThroughputCommand {
+            service_id: S::SERVICE_ID,
+            reply_channel: ReplyChannel(reply),
+        }))
+        .await;
+        receiver.await.unwrap_or_else(|e| {
+            error!(error=?e, "Error receiving throughput for service {}", S::SERVICE_ID);
+            None
+        })
+    }
+
     /// Send a shutdown signal to the overwatch runner
+    /// Once the runner stops it aborts the running services, closing their relays
     pub async fn shutdown(&mut self) {
         info!("Shutting down Overwatch");
         if let Err(e) = self
@@ -69,19 +300,85 @@ impl OverwatchHandle {
         }
     }
 
+    /// Update the settings of every service
+    /// Returns whether the update was applied, it is all or nothing: if any service rejects its
+    /// settings none of them is updated.
     #[instrument(skip(self))]
-    pub async fn update_settings<S: Services>(&mut self, settings: S::Settings) {
-        if let Err(e) = self
-            .sender
-            .send(OverwatchCommand::Settings(SettingsCommand(Box::new(
-                settings,
-            ))))
-            .await
-        {
-            error!(error=?e, "Error updating settings")
+    pub async fn update_settings<S: Services>(
+        &mut self,
+        settings: S::Settings,
+    ) -> Result<(), Error> {
+        let (reply, receiver) = oneshot::channel();
+        self.send(OverwatchCommand::Settings(SettingsCommand {
+            settings: Box::new(settings),
+            reply_channel: ReplyChannel(reply),
+        }))
+        .await;
+        receiver.await.unwrap_or_else(|e| {
+            error!(error=?e, "Error receiving settings update outcome");
+            Err(Error::RunnerUnavailable)
+        })
+    }
+
+    /// Start a service left out of the app startup with
+    /// [`crate::overwatch::OverwatchRunnerBuilder::defer_start`]
+    /// Messages sent to it so far are waiting in its relay buffer, it receives them first.
+    /// Fails with [`Error::NotDeferred`] for services that were not deferred or already started.
+    pub async fn start(&mut self, service_id: ServiceId) -> Result<(), Error> {
+        let (reply, receiver) = oneshot::channel();
+        self.send(OverwatchCommand::Start(StartCommand {
+            service_id,
+            reply_channel: ReplyChannel(reply),
+        }))
+        .await;
+        receiver.await.unwrap_or_else(|e| {
+            error!(error=?e, "Error receiving service start outcome");
+            Err(Error::RunnerUnavailable)
+        })
+    }
+
+    /// Request the current settings of every service, as the app settings they were started with
+    /// They reflect the updates applied since startup, so they can be used to capture the
+    /// effective configuration of a running app.
+    /// Returns `None` if the runner is not available or `S` is not the type it runs.
+    pub async fn export_config<S: Services>(&mut self) -> Option<S::Settings> {
+        let (reply, receiver) = oneshot::channel();
+        self.send(OverwatchCommand::ExportConfig(ExportConfigCommand {
+            reply_channel: ReplyChannel(reply),
+        }))
+        .await;
+        match receiver.await {
+            Ok(settings) => settings
+                .downcast::<S::Settings>()
+                .ok()
+                .map(|settings| *settings),
+            Err(e) => {
+                error!(error=?e, "Error receiving services settings");
+                None
+            }
         }
     }
 
+    /// Drive the app settings from a reactive source
+    /// Every item is a whole app settings update, it is decomposed and pushed to each service as
+    /// [`Self::update_settings`] does. Updates are not diffed, every service is notified on each
+    /// item even if its own settings did not change. The returned task finishes once the source
+    /// stream ends.
+    pub fn set_settings_source_stream<S, St>(&self, source: St) -> JoinHandle<()>
+    where
+        S: Services + 'static,
+        St: Stream<Item = S::Settings> + Send + 'static,
+    {
+        let mut handle = self.clone();
+        self.runtime_handle.spawn(async move {
+            let mut source = Box::pin(source);
+            while let Some(settings) = source.next().await {
+                // rejected updates are logged by the runner, the next item may be valid
+                let _ = handle.update_settings::<S>(settings).await;
+            }
+        })
+    }
+
     pub fn runtime(&self) -> &Handle {
         &self.runtime_handle
     }
@@ -1,29 +1,56 @@
 pub mod commands;
+pub mod connections;
+pub mod discovery;
 pub mod handle;
+pub mod pool;
+pub mod resources;
 // std
 
-use std::any::Any;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::future::Future;
+#[cfg(feature = "testing")]
+use std::io;
+#[cfg(feature = "testing")]
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
 
 // crates
 
 use async_trait::async_trait;
+use futures::future::AbortHandle;
+#[cfg(feature = "testing")]
+use serde::Serialize;
 use thiserror::Error;
 use tokio::runtime::{Handle, Runtime};
 use tokio::sync::mpsc::Receiver;
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
-use tracing::{info, instrument};
+use tracing::{error, info, instrument, warn};
 
 // internal
 
 use crate::overwatch::commands::{
-    OverwatchCommand, OverwatchLifeCycleCommand, RelayCommand, SettingsCommand,
+    AbortHandleCommand, ExportConfigCommand, OverwatchCommand, OverwatchLifeCycleCommand,
+    RelayCommand, SettingsCommand, StartCommand, StateWatcherCommand, ThroughputCommand,
 };
+use crate::overwatch::discovery::{NoDiscovery, ServiceDiscovery};
 use crate::overwatch::handle::OverwatchHandle;
-use crate::services::relay::RelayResult;
+use crate::overwatch::pool::WorkerPool;
+use crate::overwatch::resources::{AnyResource, ManagedResource, Resources};
+#[cfg(feature = "chaos")]
+use crate::services::chaos::{FaultInjection, FaultInjector};
+#[cfg(feature = "testing")]
+use crate::services::record::{AnyRecorder, MessageRecorder};
+use crate::services::relay::{BufferBudget, RelayBufferLimits, RelayResult};
+use crate::services::settings::SettingsError;
+use crate::services::state::AnyStateWatcher;
+#[cfg(feature = "testing")]
+use crate::services::ServiceData;
 use crate::services::{ServiceError, ServiceId};
+use crate::utils::clock::{Clock, SystemClock};
 use crate::utils::runtime::default_multithread_runtime;
 
 /// Overwatch base error type
@@ -34,6 +61,20 @@ pub enum Error {
 
     #[error("Service {service_id} is unavailable")]
     Unavailable { service_id: ServiceId },
+
+    #[error(transparent)]
+    Settings(#[from] SettingsError),
+
+    #[error("settings rejected for services {failures:?}")]
+    SettingsRejected {
+        failures: Vec<(ServiceId, SettingsError)>,
+    },
+
+    #[error("overwatch runner is unavailable")]
+    RunnerUnavailable,
+
+    #[error("Service {service_id} is not waiting to be started")]
+    NotDeferred { service_id: ServiceId },
 }
 
 /// Signal sent so overwatch finish execution
@@ -63,14 +104,40 @@ pub trait Services: Send + Sync {
     /// Start all services attached to the trait implementer
     fn start_all(&mut self) -> Result<(), Error>;
 
+    /// Identifiers of the services attached to the trait implementer, in declaration order,
+    /// which is the order [`Services::start_all`] starts them in
+    fn service_ids(&self) -> Vec<ServiceId>;
+
+    /// Whether the application can not run without the service, see
+    /// [`crate::services::ServiceData::CRITICAL`]
+    fn is_critical(&self, service_id: ServiceId) -> bool;
+
     /// Stop a service attached to the trait implementer
     fn stop(&mut self, service_id: ServiceId) -> Result<(), Error>;
 
     /// Request communication relay to one of the services
     fn request_relay(&mut self, service_id: ServiceId) -> RelayResult;
 
+    /// Request a watcher of one of the services state
+    /// Returns `None` if the service is not attached to the trait implementer
+    fn request_state_watcher(&self, service_id: ServiceId) -> Option<AnyStateWatcher>;
+
+    /// Request the moving average of messages per second sent to one of the services
+    /// Returns `None` if the service is not attached to the trait implementer. Services not
+    /// started yet count the messages buffered for them.
+    fn request_throughput(&self, service_id: ServiceId) -> Option<f64>;
+
+    /// Request the raw abort handle of one of the services
+    /// Returns `None` if the service is not running
+    fn request_abort_handle(&mut self, service_id: ServiceId) -> Option<AbortHandle>;
+
     /// Update service settings
+    /// Settings are applied to every service or to none of them, if any service rejects its
+    /// settings the rest keep their current ones.
     fn update_settings(&mut self, settings: Self::Settings) -> Result<(), Error>;
+
+    /// Current settings of every service, including the updates applied since startup
+    fn settings(&self) -> Self::Settings;
 }
 
 /// `OverwatchRunner` is the entity that handles a running overwatch
@@ -79,8 +146,12 @@ pub trait Services: Send + Sync {
 /// application lifecycle.
 pub struct OverwatchRunner<S: Services> {
     services: S,
-    #[allow(unused)]
     handle: OverwatchHandle,
+    discovery: Box<dyn ServiceDiscovery>,
+    /// Services registered in the discovery backend
+    discovered: Vec<ServiceId>,
+    /// Services left out of the startup that were not started yet
+    deferred: Vec<ServiceId>,
     finish_signal_sender: oneshot::Sender<()>,
 }
 
@@ -88,31 +159,356 @@ pub struct OverwatchRunner<S: Services> {
 /// it is used when creating the `tokio::runtime::Runtime` that Overwatch uses internally
 pub const OVERWATCH_THREAD_NAME: &str = "Overwatch";
 
-impl<S> OverwatchRunner<S>
+/// Outcome of starting the services of an overwatch application
+#[derive(Debug, Default)]
+pub struct StartupReport {
+    /// Services running, in startup order
+    pub started: Vec<ServiceId>,
+    /// Non critical services that failed to start in best-effort mode
+    pub failed: Vec<(ServiceId, Error)>,
+}
+
+/// Hook called once every service is running
+pub type OnReadyHook = Box<dyn FnOnce(OverwatchHandle) + Send + 'static>;
+
+/// Boxed service main loop future
+pub type ServiceFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Hook wrapping every service main loop before it is spawned
+#[derive(Clone)]
+pub struct SpawnHook(Arc<dyn Fn(ServiceId, ServiceFuture) -> ServiceFuture + Send + Sync>);
+
+impl SpawnHook {
+    pub fn new(
+        hook: impl Fn(ServiceId, ServiceFuture) -> ServiceFuture + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(hook))
+    }
+
+    /// Wrap the main loop of the service
+    pub fn wrap(&self, service_id: ServiceId, service: ServiceFuture) -> ServiceFuture {
+        (self.0)(service_id, service)
+    }
+}
+
+impl Debug for SpawnHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SpawnHook")
+    }
+}
+
+/// Builder of an [`OverwatchRunner`] with optional configuration
+pub struct OverwatchRunnerBuilder<S: Services> {
+    settings: S::Settings,
+    runtime: Option<Runtime>,
+    on_ready: Option<OnReadyHook>,
+    resources: HashMap<TypeId, AnyResource>,
+    managed_resources: Vec<Arc<dyn ManagedResource>>,
+    worker_pool: Option<WorkerPool>,
+    relay_buffer_cap: Option<usize>,
+    relay_buffer_limits: RelayBufferLimits,
+    discovery: Box<dyn ServiceDiscovery>,
+    best_effort: bool,
+    deferred: Vec<ServiceId>,
+    clock: Arc<dyn Clock>,
+    spawn_hook: Option<SpawnHook>,
+    #[cfg(feature = "chaos")]
+    faults: HashMap<ServiceId, FaultInjector>,
+    #[cfg(feature = "testing")]
+    recorders: HashMap<ServiceId, AnyRecorder>,
+}
+
+impl<S> OverwatchRunnerBuilder<S>
 where
     S: Services + 'static,
 {
+    /// Run Overwatch (and its services) in the provided runtime instead of a default one
+    pub fn runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    /// Register an app-wide resource shared by every service
+    /// Services fetch it by type through [`crate::services::handle::ServiceStateHandle::resource`],
+    /// registering a second resource of the same type replaces the first one.
+    pub fn resource<T: Send + Sync + 'static>(mut self, resource: T) -> Self {
+        self.resources
+            .insert(TypeId::of::<T>(), Arc::new(resource) as AnyResource);
+        self
+    }
+
+    /// Register an app-wide resource that is shut down along with the app
+    /// It is shared like any [`Self::resource`], and its [`ManagedResource::shutdown`] runs once
+    /// the runner stops, e.g. closing a [`crate::overwatch::connections::ConnectionPool`].
+    pub fn managed_resource<T: ManagedResource>(mut self, resource: T) -> Self {
+        let resource = Arc::new(resource);
+        self.resources
+            .insert(TypeId::of::<T>(), resource.clone() as AnyResource);
+        self.managed_resources.push(resource);
+        self
+    }
+
+    /// Share a worker pool running at most `max_concurrency` tasks at once between the services
+    /// Services submit work to it through
+    /// [`crate::services::handle::ServiceStateHandle::pool_spawn`].
+    pub fn worker_pool(mut self, max_concurrency: usize) -> Self {
+        self.worker_pool = Some(WorkerPool::new(max_concurrency));
+        self
+    }
+
+    /// Cap the approximate bytes buffered across every service relay
+    /// Once reached, sends wait until buffered messages are received. Messages are estimated by
+    /// their stack size, see [`BufferBudget`].
+    pub fn relay_buffer_cap(mut self, bytes: usize) -> Self {
+        self.relay_buffer_cap = Some(bytes);
+        self
+    }
+
+    /// Bound the services relay buffer sizes, see [`RelayBufferLimits`] for the defaults
+    pub fn relay_buffer_limits(mut self, relay_buffer_limits: RelayBufferLimits) -> Self {
+        self.relay_buffer_limits = relay_buffer_limits;
+        self
+    }
+
+    /// Publish the services into an external registry, see [`ServiceDiscovery`]
+    pub fn service_discovery(mut self, discovery: impl ServiceDiscovery) -> Self {
+        self.discovery = Box::new(discovery);
+        self
+    }
+
+    /// Keep running when non critical services fail to start
+    /// Failed services are logged and skipped, they are reported in
+    /// [`Overwatch::startup_report`]. Critical services failing still abort startup.
+    pub fn best_effort(mut self) -> Self {
+        self.best_effort = true;
+        self
+    }
+
+    /// Leave a service out of the app startup, start it later with [`OverwatchHandle::start`]
+    /// Its relay channel is created upfront like every other: relays to it are available right
+    /// away and messages sent through them wait in its relay buffer until it starts.
+    pub fn defer_start(mut self, service_id: ServiceId) -> Self {
+        self.deferred.push(service_id);
+        self
+    }
+
+    /// Clock services read time from, the system one by default
+    pub fn clock(mut self, clock: impl Clock) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Inject faults on the messages delivered to a service, for chaos testing
+    /// They can be tuned at runtime through [`OverwatchHandle::fault_injector`], configuring a
+    /// default [`FaultInjection`] makes a service adjustable without injecting anything upfront.
+    #[cfg(feature = "chaos")]
+    pub fn fault_injection(mut self, service_id: ServiceId, injection: FaultInjection) -> Self {
+        self.faults
+            .insert(service_id, FaultInjector::new(injection));
+        self
+    }
+
+    /// Record every message delivered to the `T` service into the file at `path`
+    /// The recording can be fed back to a service with [`crate::services::record::replay`].
+    #[cfg(feature = "testing")]
+    pub fn record_relay<T>(mut self, path: impl AsRef<Path>) -> io::Result<Self>
+    where
+        T: ServiceData,
+        T::Message: Serialize,
+    {
+        let recorder = MessageRecorder::<T::Message>::create(path)?;
+        self.recorders
+            .insert(T::SERVICE_ID, Arc::new(recorder) as AnyRecorder);
+        Ok(self)
+    }
+
+    /// Wrap every service main loop with the hook before spawning it
+    /// The hook gets the service id and its main loop, and returns the future to spawn in its
+    /// place. It is meant for cross-cutting concerns like task-local context or profiling: the
+    /// returned future must drive the provided one, services abort handles keep working on it.
+    pub fn spawn_hook(
+        mut self,
+        hook: impl Fn(ServiceId, ServiceFuture) -> ServiceFuture + Send + Sync + 'static,
+    ) -> Self {
+        self.spawn_hook = Some(SpawnHook::new(hook));
+        self
+    }
+
+    /// Call the hook once every service is running
+    /// It is not called if any of the services fails to start, even non critical ones in
+    /// best-effort mode. Services left out with [`Self::defer_start`] are not waited for, the
+    /// hook is called before they start.
+    pub fn on_ready(mut self, on_ready: impl FnOnce(OverwatchHandle) + Send + 'static) -> Self {
+        self.on_ready = Some(Box::new(on_ready));
+        self
+    }
+
     /// Start the Overwatch runner process
-    /// It creates the `tokio::runtime::Runtime`, initialize the [`Services`] and start listening for
-    /// Overwatch related tasks.
-    /// Returns the [`Overwatch`] instance that handles this runner.
-    pub fn run(settings: S::Settings, runtime: Option<Runtime>) -> Overwatch {
+    /// It creates the `tokio::runtime::Runtime` (unless one was provided), initialize and start
+    /// the [`Services`] and start listening for Overwatch related tasks.
+    /// Returns the [`Overwatch`] instance that handles this runner, or the error of the first
+    /// service failing to start.
+    pub fn run(self) -> Result<Overwatch, Error> {
+        let Self {
+            settings,
+            runtime,
+            on_ready,
+            resources,
+            managed_resources,
+            worker_pool,
+            relay_buffer_cap,
+            relay_buffer_limits,
+            discovery,
+            best_effort,
+            deferred,
+            clock,
+            spawn_hook,
+            #[cfg(feature = "chaos")]
+            faults,
+            #[cfg(feature = "testing")]
+            recorders,
+        } = self;
         let runtime = runtime.unwrap_or_else(default_multithread_runtime);
 
         let (finish_signal_sender, finish_runner_signal) = tokio::sync::oneshot::channel();
         let (commands_sender, commands_receiver) = tokio::sync::mpsc::channel(16);
-        let handle = OverwatchHandle::new(runtime.handle().clone(), commands_sender);
-        let services = S::new(settings, handle.clone());
+        let buffer_budget = relay_buffer_cap
+            .map(BufferBudget::with_cap)
+            .unwrap_or_default();
+        let mut handle = OverwatchHandle::new(runtime.handle().clone(), commands_sender)
+            .with_resources(Resources::new(resources).with_managed(managed_resources))
+            .with_buffer_budget(Arc::new(buffer_budget))
+            .with_relay_buffer_limits(relay_buffer_limits)
+            .with_clock(clock);
+        #[cfg(feature = "chaos")]
+        {
+            handle = handle.with_faults(faults);
+        }
+        #[cfg(feature = "testing")]
+        {
+            handle = handle.with_recorders(recorders);
+        }
+        if let Some(worker_pool) = worker_pool {
+            handle = handle.with_worker_pool(worker_pool);
+        }
+        if let Some(spawn_hook) = spawn_hook {
+            handle = handle.with_spawn_hook(spawn_hook);
+        }
+        let mut services = S::new(settings, handle.clone());
+        let startup_report = {
+            // services are initialized within the runtime context
+            let _runtime_context = runtime.enter();
+            if best_effort {
+                Self::start_best_effort(&mut services, &deferred)?
+            } else if !deferred.is_empty() {
+                Self::start_not_deferred(&mut services, &deferred)?
+            } else {
+                // TODO: this probably need to be manually done, or at least handled by a flag
+                services.start_all()?;
+                StartupReport {
+                    started: services.service_ids(),
+                    failed: Vec::new(),
+                }
+            }
+        };
+        for &service_id in &startup_report.started {
+            discovery.register(service_id);
+        }
         let runner = OverwatchRunner {
             services,
             handle: handle.clone(),
+            discovery,
+            discovered: startup_report.started.clone(),
+            deferred,
             finish_signal_sender,
         };
         runtime.spawn(async move { runner.run_(commands_receiver).await });
-        Overwatch {
+        if let Some(on_ready) = on_ready {
+            if startup_report.failed.is_empty() {
+                on_ready(handle.clone());
+            }
+        }
+        Ok(Overwatch {
             runtime,
             handle,
             finish_runner_signal,
+            startup_report,
+        })
+    }
+
+    fn start_not_deferred(
+        services: &mut S,
+        deferred: &[ServiceId],
+    ) -> Result<StartupReport, Error> {
+        let mut report = StartupReport::default();
+        for service_id in services.service_ids() {
+            if !deferred.contains(&service_id) {
+                services.start(service_id)?;
+                report.started.push(service_id);
+            }
+        }
+        Ok(report)
+    }
+
+    fn start_best_effort(services: &mut S, deferred: &[ServiceId]) -> Result<StartupReport, Error> {
+        let mut report = StartupReport::default();
+        for service_id in services.service_ids() {
+            if deferred.contains(&service_id) {
+                continue;
+            }
+            match services.start(service_id) {
+                Ok(()) => report.started.push(service_id),
+                Err(e) if !services.is_critical(service_id) => {
+                    warn!(error=?e, "Non critical service {} failed to start", service_id);
+                    report.failed.push((service_id, e));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(report)
+    }
+}
+
+impl<S> OverwatchRunner<S>
+where
+    S: Services + 'static,
+{
+    /// Start the Overwatch runner process with the default configuration
+    /// It creates the `tokio::runtime::Runtime` (unless one was provided), initialize the
+    /// [`Services`] and start listening for Overwatch related tasks.
+    /// Returns the [`Overwatch`] instance that handles this runner.
+    ///
+    /// # Panics
+    ///
+    /// If any of the services fails to start, use [`OverwatchRunner::builder`] to handle it.
+    pub fn run(settings: S::Settings, runtime: Option<Runtime>) -> Overwatch {
+        let mut builder = Self::builder(settings);
+        if let Some(runtime) = runtime {
+            builder = builder.runtime(runtime);
+        }
+        builder.run().expect("Services to start running")
+    }
+
+    /// Configure an Overwatch runner process before starting it
+    pub fn builder(settings: S::Settings) -> OverwatchRunnerBuilder<S> {
+        OverwatchRunnerBuilder {
+            settings,
+            runtime: None,
+            on_ready: None,
+            resources: HashMap::new(),
+            managed_resources: Vec::new(),
+            worker_pool: None,
+            relay_buffer_cap: None,
+            relay_buffer_limits: RelayBufferLimits::default(),
+            discovery: Box::new(NoDiscovery),
+            best_effort: false,
+            deferred: Vec::new(),
+            clock: Arc::new(SystemClock),
+            spawn_hook: None,
+            #[cfg(feature = "chaos")]
+            faults: HashMap::new(),
+            #[cfg(feature = "testing")]
+            recorders: HashMap::new(),
         }
     }
 
@@ -120,17 +516,30 @@ where
     async fn run_(self, mut receiver: Receiver<OverwatchCommand>) {
         let Self {
             mut services,
-            handle: _,
+            handle,
+            discovery,
+            mut discovered,
+            mut deferred,
             finish_signal_sender,
         } = self;
-        // TODO: this probably need to be manually done, or at least handled by a flag
-        services.start_all().expect("Services to start running");
         while let Some(command) = receiver.recv().await {
             info!(command = ?command, "Overwatch command received");
             match command {
                 OverwatchCommand::Relay(relay_command) => {
                     Self::handle_relay(&mut services, relay_command).await;
                 }
+                OverwatchCommand::AbortHandle(abort_handle_command) => {
+                    Self::handle_abort_handle(&mut services, abort_handle_command).await;
+                }
+                OverwatchCommand::StateWatcher(state_watcher_command) => {
+                    Self::handle_state_watcher(&services, state_watcher_command).await;
+                }
+                OverwatchCommand::Throughput(throughput_command) => {
+                    Self::handle_throughput(&services, throughput_command).await;
+                }
+                OverwatchCommand::ExportConfig(export_config_command) => {
+                    Self::handle_export_config(&services, export_config_command).await;
+                }
                 OverwatchCommand::ServiceLifeCycle(_) => {
                     unimplemented!("Services life cycle is still not supported!");
                 }
@@ -145,8 +554,29 @@ where
                 OverwatchCommand::Settings(settings) => {
                     Self::handle_settings_update(&mut services, settings).await;
                 }
+                OverwatchCommand::Start(start_command) => {
+                    Self::handle_start(
+                        &mut services,
+                        start_command,
+                        &*discovery,
+                        &mut discovered,
+                        &mut deferred,
+                    )
+                    .await;
+                }
+            }
+        }
+        // dropping the running services closes their relays, so relays held from outside the
+        // app (e.g. bridges from other apps) see it stop
+        for service_id in services.service_ids() {
+            if let Some(abort_handle) = services.request_abort_handle(service_id) {
+                abort_handle.abort();
             }
         }
+        for service_id in discovered.into_iter().rev() {
+            discovery.deregister(service_id);
+        }
+        handle.resources().shutdown();
         // signal that we finished execution
         finish_signal_sender
             .send(())
@@ -167,12 +597,103 @@ where
         }
     }
 
+    async fn handle_abort_handle(services: &mut S, command: AbortHandleCommand) {
+        let AbortHandleCommand {
+            service_id,
+            reply_channel,
+        } = command;
+        if reply_channel
+            .reply(services.request_abort_handle(service_id))
+            .await
+            .is_err()
+        {
+            info!("Error replying abort handle for service {}", service_id)
+        }
+    }
+
+    async fn handle_state_watcher(services: &S, command: StateWatcherCommand) {
+        let StateWatcherCommand {
+            service_id,
+            reply_channel,
+        } = command;
+        if reply_channel
+            .reply(services.request_state_watcher(service_id))
+            .await
+            .is_err()
+        {
+            info!("Error replying state watcher for service {}", service_id)
+        }
+    }
+
+    async fn handle_throughput(services: &S, command: ThroughputCommand) {
+        let ThroughputCommand {
+            service_id,
+            reply_channel,
+        } = command;
+        if reply_channel
+            .reply(services.request_throughput(service_id))
+            .await
+            .is_err()
+        {
+            info!("Error replying throughput for service {}", service_id)
+        }
+    }
+
+    async fn handle_export_config(services: &S, command: ExportConfigCommand) {
+        let ExportConfigCommand { reply_channel } = command;
+        if reply_channel
+            .reply(Box::new(services.settings()))
+            .await
+            .is_err()
+        {
+            info!("Error replying services settings")
+        }
+    }
+
+    async fn handle_start(
+        services: &mut S,
+        command: StartCommand,
+        discovery: &dyn ServiceDiscovery,
+        discovered: &mut Vec<ServiceId>,
+        deferred: &mut Vec<ServiceId>,
+    ) {
+        let StartCommand {
+            service_id,
+            reply_channel,
+        } = command;
+        // a second start would spawn another instance of the service next to the running one
+        let result = if !deferred.contains(&service_id)
+            || services.request_abort_handle(service_id).is_some()
+        {
+            Err(Error::NotDeferred { service_id })
+        } else {
+            services.start(service_id)
+        };
+        match &result {
+            Ok(()) => {
+                deferred.retain(|deferred_id| *deferred_id != service_id);
+                discovery.register(service_id);
+                discovered.push(service_id);
+            }
+            Err(e) => error!(error=?e, "Error starting service {}", service_id),
+        }
+        if reply_channel.reply(result).await.is_err() {
+            info!("Error replying service start outcome")
+        }
+    }
+
     async fn handle_settings_update(services: &mut S, command: SettingsCommand) {
-        let SettingsCommand(settings) = command;
+        let SettingsCommand {
+            settings,
+            reply_channel,
+        } = command;
         if let Ok(settings) = settings.downcast::<S::Settings>() {
-            if let Err(e) = services.update_settings(*settings) {
-                // TODO: add proper logging
-                dbg!(e);
+            let result = services.update_settings(*settings);
+            if let Err(e) = &result {
+                error!(error=?e, "Error updating settings");
+            }
+            if reply_channel.reply(result).await.is_err() {
+                info!("Error replying settings update outcome")
             }
         } else {
             unreachable!("Statically should always be of the correct type");
@@ -186,6 +707,7 @@ pub struct Overwatch {
     runtime: Runtime,
     handle: OverwatchHandle,
     finish_runner_signal: oneshot::Receiver<FinishOverwatchSignal>,
+    startup_report: StartupReport,
 }
 
 impl Overwatch {
@@ -195,6 +717,11 @@ impl Overwatch {
         &self.handle
     }
 
+    /// Services started, and the ones that failed to when running in best-effort mode
+    pub fn startup_report(&self) -> &StartupReport {
+        &self.startup_report
+    }
+
     /// Get the underllaying tokio runtime handle
     pub fn runtime(&self) -> &Handle {
         self.runtime.handle()
@@ -228,7 +755,9 @@ mod test {
     use crate::overwatch::handle::OverwatchHandle;
     use crate::overwatch::{Error, OverwatchRunner, Services};
     use crate::services::relay::{RelayError, RelayResult};
+    use crate::services::state::AnyStateWatcher;
     use crate::services::ServiceId;
+    use futures::future::AbortHandle;
     use std::time::Duration;
     use tokio::time::sleep;
 
@@ -249,6 +778,14 @@ mod test {
             Ok(())
         }
 
+        fn service_ids(&self) -> Vec<ServiceId> {
+            Vec::new()
+        }
+
+        fn is_critical(&self, _service_id: ServiceId) -> bool {
+            true
+        }
+
         fn stop(&mut self, service_id: ServiceId) -> Result<(), Error> {
             Err(Error::Unavailable { service_id })
         }
@@ -257,9 +794,23 @@ mod test {
             Err(RelayError::InvalidRequest { to: service_id })
         }
 
+        fn request_state_watcher(&self, _service_id: ServiceId) -> Option<AnyStateWatcher> {
+            None
+        }
+
+        fn request_throughput(&self, _service_id: ServiceId) -> Option<f64> {
+            None
+        }
+
+        fn request_abort_handle(&mut self, _service_id: ServiceId) -> Option<AbortHandle> {
+            None
+        }
+
         fn update_settings(&mut self, _settings: Self::Settings) -> Result<(), Error> {
             Ok(())
         }
+
+        fn settings(&self) -> Self::Settings {}
     }
 
     #[test]
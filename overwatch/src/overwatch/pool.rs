@@ -0,0 +1,52 @@
+// std
+use std::future::Future;
+use std::sync::Arc;
+// crates
+use tokio::runtime::Handle;
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+// internal
+
+/// Worker pool shared by the services of an overwatch application
+/// It caps how many submitted tasks run at the same time across every service. Tasks waiting for
+/// a free worker are served in submission order, so a bursty service cannot starve the others.
+#[derive(Clone, Debug)]
+pub struct WorkerPool {
+    workers: Arc<Semaphore>,
+    max_concurrency: usize,
+}
+
+impl WorkerPool {
+    pub fn new(max_concurrency: usize) -> Self {
+        Self {
+            workers: Arc::new(Semaphore::new(max_concurrency)),
+            max_concurrency,
+        }
+    }
+
+    /// Maximum amount of tasks running at once
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Amount of workers free to pick a task right now
+    pub fn available_workers(&self) -> usize {
+        self.workers.available_permits()
+    }
+
+    /// Spawn a task in the runtime that only starts running once a worker is free
+    pub fn spawn<F>(&self, runtime: &Handle, task: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let workers = self.workers.clone();
+        runtime.spawn(async move {
+            let _worker = workers
+                .acquire_owned()
+                .await
+                .expect("Worker pool semaphore is never closed");
+            task.await
+        })
+    }
+}
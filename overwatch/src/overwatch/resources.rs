@@ -0,0 +1,67 @@
+// std
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+// crates
+// internal
+
+/// Type erased shared resource
+pub type AnyResource = Arc<dyn Any + Send + Sync + 'static>;
+
+/// Shared resource with a shutdown step
+/// Registered through [`crate::overwatch::OverwatchRunnerBuilder::managed_resource`], it is shut
+/// down by the overwatch runner once the app stops.
+pub trait ManagedResource: Send + Sync + 'static {
+    /// Release whatever the resource holds, services may still hold the resource afterwards
+    fn shutdown(&self);
+}
+
+/// App-wide registry of shared resources indexed by type
+/// Resources are registered when building the overwatch runner and shared between every service,
+/// so common infrastructure (connection pools, clients...) is constructed only once.
+#[derive(Clone, Default)]
+pub struct Resources {
+    resources: Arc<HashMap<TypeId, AnyResource>>,
+    managed: Arc<Vec<Arc<dyn ManagedResource>>>,
+}
+
+impl Resources {
+    pub fn new(resources: HashMap<TypeId, AnyResource>) -> Self {
+        Self {
+            resources: Arc::new(resources),
+            managed: Arc::default(),
+        }
+    }
+
+    /// Shut the provided resources down along with the registry, see [`Self::shutdown`]
+    pub fn with_managed(mut self, managed: Vec<Arc<dyn ManagedResource>>) -> Self {
+        self.managed = Arc::new(managed);
+        self
+    }
+
+    /// Get the registered resource of type `T`
+    /// Returns `None` if no resource of that type was registered
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.resources
+            .get(&TypeId::of::<T>())
+            .cloned()
+            .and_then(|resource| resource.downcast::<T>().ok())
+    }
+
+    /// Shut every managed resource down, in registration order
+    pub fn shutdown(&self) {
+        for resource in self.managed.iter() {
+            resource.shutdown();
+        }
+    }
+}
+
+impl Debug for Resources {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Resources")
+            .field("count", &self.resources.len())
+            .field("managed", &self.managed.len())
+            .finish()
+    }
+}
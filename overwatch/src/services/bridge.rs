@@ -0,0 +1,79 @@
+// std
+// crates
+use futures::future::{select, Either};
+use futures::pin_mut;
+use tokio::task::JoinHandle;
+// internal
+use crate::overwatch::handle::OverwatchHandle;
+use crate::services::relay::{relay, InboundRelay, OutboundRelay, RelayError};
+use crate::services::ServiceCore;
+
+/// Relay from one overwatch application to a service of another one in the same process
+///
+/// Services of the source app send through [`Self::relay`], their messages are forwarded to the
+/// target service by a task running in the target app. The bridge closes when either side stops:
+/// once the target service stops, e.g. because its app shut down, the source relays get
+/// disconnected, and once every source relay is dropped the bridge lets go of the target service.
+pub struct BridgeRelay<M> {
+    outbound_relay: OutboundRelay<M>,
+    forwarder: JoinHandle<()>,
+}
+
+impl<M: Send + 'static> BridgeRelay<M> {
+    /// Bridge to the `S` service of the app behind `target`
+    /// Up to `buffer_size` messages wait in the bridge while the target service relay is full.
+    pub async fn connect<S>(
+        target: &OverwatchHandle,
+        buffer_size: usize,
+    ) -> Result<Self, RelayError>
+    where
+        S: ServiceCore<Message = M>,
+    {
+        let target_relay = target.relay::<S>().connect().await?;
+        let (inbound_relay, outbound_relay) = relay(buffer_size);
+        let forwarder = target.runtime().spawn(forward(inbound_relay, target_relay));
+        Ok(Self {
+            outbound_relay,
+            forwarder,
+        })
+    }
+
+    /// Relay for the source app services, messages sent through it cross the bridge
+    pub fn relay(&self) -> OutboundRelay<M> {
+        self.outbound_relay.clone()
+    }
+
+    /// Let go of this handle end and wait until the bridge is closed
+    /// Resolves once the target service stops, or once every relay handed out by
+    /// [`Self::relay`] is dropped.
+    pub async fn closed(self) {
+        let Self {
+            outbound_relay,
+            forwarder,
+        } = self;
+        drop(outbound_relay);
+        let _ = forwarder.await;
+    }
+}
+
+async fn forward<M>(mut source: InboundRelay<M>, target: OutboundRelay<M>) {
+    loop {
+        let message = {
+            let received = source.recv();
+            let closed = target.closed();
+            pin_mut!(received, closed);
+            match select(received, closed).await {
+                Either::Left((message, _)) => message,
+                // target service stopped
+                Either::Right(_) => None,
+            }
+        };
+        let message = match message {
+            Some(message) => message,
+            None => break,
+        };
+        if target.send(message).await.is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// std
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+// crates
+// internal
+
+/// Faults injected on the messages delivered to a service
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FaultInjection {
+    /// Fraction of the messages dropped before being delivered, from `0.0` to `1.0`
+    pub drop_rate: f64,
+    /// Delay added before delivering every message that is not dropped
+    pub delay: Duration,
+}
+
+/// Runtime adjustable faults of a service relay
+/// Clones share the same faults, so updating them through one clone affects the relay right away.
+/// Drops are deterministic: a `drop_rate` of `0.25` drops exactly one out of every four messages,
+/// which keeps chaos test runs reproducible.
+#[derive(Clone, Debug, Default)]
+pub struct FaultInjector {
+    faults: Arc<Mutex<Faults>>,
+}
+
+#[derive(Debug, Default)]
+struct Faults {
+    injection: FaultInjection,
+    drop_credit: f64,
+}
+
+impl FaultInjector {
+    pub fn new(injection: FaultInjection) -> Self {
+        let injector = Self::default();
+        injector.set(injection);
+        injector
+    }
+
+    /// Faults currently injected
+    pub fn get(&self) -> FaultInjection {
+        self.faults.lock().unwrap().injection
+    }
+
+    /// Replace the injected faults
+    pub fn set(&self, injection: FaultInjection) {
+        let mut faults = self.faults.lock().unwrap();
+        faults.injection = FaultInjection {
+            drop_rate: injection.drop_rate.clamp(0.0, 1.0),
+            ..injection
+        };
+        faults.drop_credit = 0.0;
+    }
+
+    /// Delay to wait before delivering the next message, `None` if it has to be dropped
+    pub(crate) fn delivery_delay(&self) -> Option<Duration> {
+        let mut faults = self.faults.lock().unwrap();
+        faults.drop_credit += faults.injection.drop_rate;
+        if faults.drop_credit >= 1.0 {
+            faults.drop_credit -= 1.0;
+            None
+        } else {
+            Some(faults.injection.delay)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::services::chaos::{FaultInjection, FaultInjector};
+    use std::time::Duration;
+
+    #[test]
+    fn drops_follow_drop_rate() {
+        let injector = FaultInjector::new(FaultInjection {
+            drop_rate: 0.25,
+            delay: Duration::from_millis(5),
+        });
+        let delivered: Vec<_> = (0..8).map(|_| injector.delivery_delay()).collect();
+        let dropped = delivered.iter().filter(|delay| delay.is_none()).count();
+        assert_eq!(dropped, 2);
+        assert!(delivered
+            .iter()
+            .flatten()
+            .all(|delay| *delay == Duration::from_millis(5)));
+    }
+}
@@ -0,0 +1,171 @@
+// std
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+// crates
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use tokio::sync::{broadcast, oneshot};
+use tokio_stream::wrappers::BroadcastStream;
+use tracing::error;
+// internal
+use crate::services::handle::ServiceStateHandle;
+use crate::services::relay::{OutboundRelay, RelayError, RelayMessage};
+use crate::services::state::{NoOperator, NoState};
+use crate::services::{ServiceCore, ServiceData, ServiceId};
+
+/// Event bus topic identification type
+pub type Topic = String;
+
+/// Events carried by an [`EventBusService`]
+/// Each event type gets its own bus service, identified by [`BusEvent::BUS_ID`], so an
+/// application can run a bus per event type.
+pub trait BusEvent: Clone + Debug + Send + Sync + 'static {
+    /// Identifier of the bus service for this event type, it must be unique within the app
+    const BUS_ID: ServiceId;
+}
+
+/// Messages understood by the [`EventBusService`]
+#[derive(Debug)]
+pub enum EventBusMessage<E> {
+    /// Publish an event to every subscriber of a topic
+    Publish { topic: Topic, event: E },
+    /// Subscribe to a topic, a receiver for its events is sent back through the reply channel
+    Subscribe {
+        topic: Topic,
+        reply_channel: oneshot::Sender<broadcast::Receiver<E>>,
+    },
+}
+
+impl<E: 'static> RelayMessage for EventBusMessage<E> {}
+
+/// [`EventBusService`] settings
+#[derive(Clone, Debug)]
+pub struct EventBusSettings {
+    /// Amount of events buffered per topic before slow subscribers start lagging
+    pub topic_capacity: usize,
+}
+
+impl Default for EventBusSettings {
+    fn default() -> Self {
+        Self { topic_capacity: 16 }
+    }
+}
+
+/// Topic based publish/subscribe service
+/// Producers publish events to named topics and consumers subscribe to the topics they are
+/// interested in, without knowing about each other.
+pub struct EventBusService<E: BusEvent> {
+    service_state: ServiceStateHandle<Self>,
+    _event: PhantomData<E>,
+}
+
+impl<E: BusEvent> ServiceData for EventBusService<E> {
+    const SERVICE_ID: ServiceId = E::BUS_ID;
+    type Settings = EventBusSettings;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = EventBusMessage<E>;
+}
+
+#[async_trait]
+impl<E: BusEvent> ServiceCore for EventBusService<E> {
+    fn init(service_state: ServiceStateHandle<Self>) -> Self {
+        Self {
+            service_state,
+            _event: PhantomData,
+        }
+    }
+
+    async fn run(self) {
+        let Self {
+            service_state:
+                ServiceStateHandle {
+                    mut inbound_relay,
+                    mut settings_reader,
+                    ..
+                },
+            ..
+        } = self;
+        let EventBusSettings { topic_capacity } = settings_reader.get_updated_settings();
+        let mut topics: HashMap<Topic, broadcast::Sender<E>> = HashMap::new();
+
+        while let Some(message) = inbound_relay.recv().await {
+            match message {
+                EventBusMessage::Publish { topic, event } => {
+                    // events published to a topic nobody listens to are just dropped
+                    if let Some(sender) = topics.get(&topic) {
+                        let _ = sender.send(event);
+                    }
+                }
+                EventBusMessage::Subscribe {
+                    topic,
+                    reply_channel,
+                } => {
+                    let receiver = topics
+                        .entry(topic)
+                        .or_insert_with(|| broadcast::channel(topic_capacity).0)
+                        .subscribe();
+                    if reply_channel.send(receiver).is_err() {
+                        error!("Error replying event bus subscription");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Client side of the [`EventBusService`]
+/// Wraps a relay to the service with the publish/subscribe operations
+pub struct EventBusRelay<E> {
+    relay: OutboundRelay<EventBusMessage<E>>,
+}
+
+impl<E> Clone for EventBusRelay<E> {
+    fn clone(&self) -> Self {
+        Self {
+            relay: self.relay.clone(),
+        }
+    }
+}
+
+impl<E> EventBusRelay<E>
+where
+    E: Clone + Send + 'static,
+{
+    pub fn new(relay: OutboundRelay<EventBusMessage<E>>) -> Self {
+        Self { relay }
+    }
+
+    /// Publish an event to a topic
+    pub async fn publish(&self, topic: impl Into<Topic>, event: E) -> Result<(), RelayError> {
+        self.relay
+            .send(EventBusMessage::Publish {
+                topic: topic.into(),
+                event,
+            })
+            .await
+            .map_err(|(e, _)| e)
+    }
+
+    /// Subscribe to a topic
+    /// Returns a stream of the events published to it from now on. Events missed by a lagging
+    /// subscriber are skipped.
+    pub async fn subscribe(
+        &self,
+        topic: impl Into<Topic>,
+    ) -> Result<impl Stream<Item = E> + Unpin, RelayError> {
+        let (reply_channel, receiver) = oneshot::channel();
+        self.relay
+            .send(EventBusMessage::Subscribe {
+                topic: topic.into(),
+                reply_channel,
+            })
+            .await
+            .map_err(|(e, _)| e)?;
+        let receiver = receiver
+            .await
+            .map_err(|e| RelayError::Receiver(Box::new(e)))?;
+        Ok(BroadcastStream::new(receiver).filter_map(|event| futures::future::ready(event.ok())))
+    }
+}
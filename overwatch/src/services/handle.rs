@@ -1,14 +1,22 @@
 // std
+use std::future::Future;
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
 // crates
-use futures::future::{abortable, AbortHandle};
+pub use futures::future::AbortHandle;
+use futures::future::{AbortRegistration, Abortable, Aborted};
 use tokio::runtime::Handle;
+use tokio::task::{JoinError, JoinHandle};
 use tracing::instrument;
 // internal
 use crate::overwatch::handle::OverwatchHandle;
-use crate::services::relay::{relay, InboundRelay, OutboundRelay};
-use crate::services::settings::{SettingsNotifier, SettingsUpdater};
-use crate::services::state::{StateHandle, StateOperator, StateUpdater};
+use crate::overwatch::ServiceFuture;
+use crate::services::relay::{relay_with_budget, InboundRelay, OutboundRelay, RelayError};
+use crate::services::settings::{SettingsError, SettingsNotifier, SettingsUpdater};
+use crate::services::state::{
+    state_channel, StateHandle, StateOperator, StateUpdater, StateWatcher,
+};
 use crate::services::{ServiceCore, ServiceId, ServiceState};
 
 // TODO: Abstract handle over state, to diferentiate when the service is running and when it is not
@@ -18,13 +26,25 @@ use crate::services::{ServiceCore, ServiceId, ServiceState};
 /// This is used to access different parts of the service
 pub struct ServiceHandle<S: ServiceCore> {
     /// Message channel relay
-    /// Would be None if service is not running
-    /// Will contain the channel if service is running
+    /// Created upfront, so messages can be sent to the service before it starts: they are
+    /// buffered until it runs
     outbound_relay: Option<OutboundRelay<S::Message>>,
+    /// Receiving end of the upfront relay channel, handed to the service when it starts
+    inbound_relay: Option<InboundRelay<S::Message>>,
     /// Handle to overwatch
     overwatch_handle: OverwatchHandle,
     settings: SettingsUpdater<S::Settings>,
-    initial_state: S::State,
+    /// Why the settings pipeline rejected the initial settings, if it did
+    /// The service refuses to start until valid settings are applied
+    settings_error: Option<SettingsError>,
+    /// State channel, created upfront so the state can be watched even before the service runs
+    /// The updating end is handed to the service when it starts, so the state task feeding its
+    /// operator ends along with the service
+    state_updater: Option<StateUpdater<S::State>>,
+    state_watcher: StateWatcher<S::State>,
+    /// Handle to abort the service main loop
+    /// Would be None if service is not running
+    abort_handle: Option<AbortHandle>,
     _marker: PhantomData<S>,
 }
 
@@ -45,19 +65,57 @@ pub struct ServiceStateHandle<S: ServiceCore> {
 pub struct ServiceRunner<S: ServiceCore> {
     service_state: ServiceStateHandle<S>,
     state_handle: StateHandle<S::State, S::StateOperator>,
+    abort_handle: AbortHandle,
+    abort_registration: AbortRegistration,
 }
 
 impl<S: ServiceCore> ServiceHandle<S> {
     pub fn new(settings: S::Settings, overwatch_handle: OverwatchHandle) -> Self {
-        let initial_state: S::State = S::State::from_settings(&settings);
+        Self::build(settings, overwatch_handle, None)
+    }
 
-        let settings = SettingsUpdater::new(settings);
+    /// Build a handle for a service that starts from the provided state
+    /// instead of deriving it from the settings, useful for restoring snapshots or testing
+    pub fn with_state(
+        settings: S::Settings,
+        overwatch_handle: OverwatchHandle,
+        initial_state: S::State,
+    ) -> Self {
+        Self::build(settings, overwatch_handle, Some(initial_state))
+    }
+
+    /// Initial settings go through the settings pipeline like any update: the handle keeps the
+    /// resulting settings, or the rejection when the pipeline refuses them
+    fn build(
+        settings: S::Settings,
+        overwatch_handle: OverwatchHandle,
+        initial_state: Option<S::State>,
+    ) -> Self {
+        let layers = S::settings_layers();
+        let (settings, settings_error) = match layers
+            .iter()
+            .try_fold(settings.clone(), |settings, layer| layer.apply(settings))
+        {
+            Ok(settings) => (settings, None),
+            Err(e) => (settings, Some(e)),
+        };
+        let initial_state = initial_state.unwrap_or_else(|| S::State::from_settings(&settings));
+        let settings = SettingsUpdater::new(settings).with_layers(layers);
+        let (state_watcher, state_updater) = state_channel(initial_state);
+        let (inbound_relay, outbound_relay) = relay_with_budget::<S::Message>(
+            S::SERVICE_RELAY_BUFFER_SIZE,
+            overwatch_handle.buffer_budget(),
+        );
 
         Self {
-            outbound_relay: None,
+            outbound_relay: Some(outbound_relay),
+            inbound_relay: Some(inbound_relay),
             settings,
-            initial_state,
+            settings_error,
+            state_updater: Some(state_updater),
+            state_watcher,
             overwatch_handle,
+            abort_handle: None,
             _marker: PhantomData::default(),
         }
     }
@@ -79,26 +137,121 @@ impl<S: ServiceCore> ServiceHandle<S> {
     }
 
     /// Request a relay with this service
+    /// It is available before the service starts, messages sent early wait in the relay buffer.
     pub fn relay_with(&self) -> Option<OutboundRelay<S::Message>> {
         self.outbound_relay.clone()
     }
 
+    /// Moving average of the messages per second sent to the service
+    pub fn throughput(&self) -> Option<f64> {
+        self.outbound_relay
+            .as_ref()
+            .map(|relay| relay.throughput().messages_per_second())
+    }
+
+    /// Get a watcher of the service state
+    /// It always holds the latest state, which is the initial one if the service did not update it
+    /// or is not running yet
+    pub fn state_watcher(&self) -> StateWatcher<S::State> {
+        self.state_watcher.clone()
+    }
+
+    /// Raw abort handle of the running service main loop
+    /// Aborting through it bypasses any graceful shutdown, use with caution
+    pub fn abort_handle(&self) -> Option<AbortHandle> {
+        self.abort_handle.clone()
+    }
+
     /// Update settings
-    pub fn update_settings(&self, settings: S::Settings) {
-        self.settings.update(settings)
+    /// Fails if the service settings pipeline rejects them
+    pub fn update_settings(&mut self, settings: S::Settings) -> Result<(), SettingsError> {
+        let settings = self.prepare_settings(settings)?;
+        self.publish_settings(settings);
+        Ok(())
+    }
+
+    /// Run settings through the service settings pipeline without applying them
+    pub fn prepare_settings(&self, settings: S::Settings) -> Result<S::Settings, SettingsError> {
+        self.settings.prepare(settings)
+    }
+
+    /// Apply settings already run through [`Self::prepare_settings`]
+    /// They replace rejected initial settings, so the service can start afterwards.
+    pub fn publish_settings(&mut self, settings: S::Settings) {
+        self.settings_error = None;
+        self.settings.publish(settings)
+    }
+
+    /// Current service settings
+    pub fn settings(&self) -> S::Settings {
+        self.settings.notifier().get_updated_settings()
+    }
+
+    /// Check the service relay buffer size against the overwatch relay buffer limits
+    pub fn check_relay_buffer_size(&self) -> Result<(), RelayError> {
+        self.overwatch_handle
+            .relay_buffer_limits()
+            .check(S::SERVICE_ID, S::SERVICE_RELAY_BUFFER_SIZE)
+    }
+
+    /// Check the initial settings made it through the service settings pipeline
+    pub fn check_initial_settings(&self) -> Result<(), SettingsError> {
+        match &self.settings_error {
+            Some(e) => Err(e.clone()),
+            None => Ok(()),
+        }
     }
 
     /// Build a runner for this service
     pub fn service_runner(&mut self) -> ServiceRunner<S> {
         // TODO: add proper status handling here, a service should be able to produce a runner if it is already running.
-        let (inbound_relay, outbound_relay) = relay::<S::Message>(S::SERVICE_RELAY_BUFFER_SIZE);
+        // the first runner takes the channel created upfront, later ones need a fresh channel
+        let (inbound_relay, outbound_relay) = match self.inbound_relay.take() {
+            Some(inbound_relay) => (
+                inbound_relay,
+                self.outbound_relay
+                    .clone()
+                    .expect("Outbound relay created along the inbound one"),
+            ),
+            None => relay_with_budget::<S::Message>(
+                S::SERVICE_RELAY_BUFFER_SIZE,
+                self.overwatch_handle.buffer_budget(),
+            ),
+        };
+        #[cfg(feature = "chaos")]
+        let inbound_relay = match self.overwatch_handle.fault_injector(S::SERVICE_ID) {
+            Some(faults) => inbound_relay.with_faults(faults),
+            None => inbound_relay,
+        };
+        #[cfg(feature = "testing")]
+        let inbound_relay = match self
+            .overwatch_handle
+            .message_recorder::<S::Message>(S::SERVICE_ID)
+        {
+            Some(recorder) => inbound_relay.with_recorder(recorder),
+            None => inbound_relay,
+        };
+        // same for the state channel, later runners continue from the latest state
+        let state_updater = match self.state_updater.take() {
+            Some(state_updater) => state_updater,
+            None => {
+                let (state_watcher, state_updater) =
+                    state_channel(self.state_watcher.state_cloned());
+                self.state_watcher = state_watcher;
+                state_updater
+            }
+        };
         let settings_reader = self.settings.notifier();
         // add relay channel to handle
         self.outbound_relay = Some(outbound_relay);
         let settings = self.settings.notifier().get_updated_settings();
         let operator = S::StateOperator::from_settings::<S::Settings>(settings);
-        let (state_handle, state_updater) =
-            StateHandle::<S::State, S::StateOperator>::new(self.initial_state.clone(), operator);
+        let state_handle = StateHandle::<S::State, S::StateOperator>::with_watcher(
+            self.state_watcher.clone(),
+            operator,
+        );
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        self.abort_handle = Some(abort_handle.clone());
 
         let service_state = ServiceStateHandle {
             inbound_relay,
@@ -111,6 +264,8 @@ impl<S: ServiceCore> ServiceHandle<S> {
         ServiceRunner {
             service_state,
             state_handle,
+            abort_handle,
+            abort_registration,
         }
     }
 }
@@ -119,28 +274,99 @@ impl<S: ServiceCore> ServiceStateHandle<S> {
     pub fn id(&self) -> ServiceId {
         S::SERVICE_ID
     }
+
+    /// Get the app-wide shared resource of type `T`
+    /// Returns `None` if no resource of that type was registered in the overwatch runner
+    pub fn resource<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.overwatch_handle.resource::<T>()
+    }
+
+    /// Monotonic current time, as read from the overwatch clock
+    /// Prefer it over `Instant::now` so the service time can be controlled in tests
+    pub fn now(&self) -> Instant {
+        self.overwatch_handle.clock().now()
+    }
+
+    /// Wall clock current time, as read from the overwatch clock
+    pub fn system_time(&self) -> SystemTime {
+        self.overwatch_handle.clock().system_time()
+    }
+
+    /// Submit a task to the app-wide worker pool
+    /// The task waits for a free worker, the pool concurrency cap is shared with every other
+    /// service. Returns `None` if the overwatch runner was not configured with a worker pool.
+    pub fn pool_spawn<F>(&self, task: F) -> Option<JoinHandle<F::Output>>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let pool = self.overwatch_handle.worker_pool()?;
+        Some(pool.spawn(self.overwatch_handle.runtime(), task))
+    }
+}
+
+/// Handle over a spawned service main loop
+pub struct ServiceRunnerHandle {
+    abort_handle: AbortHandle,
+    join_handle: JoinHandle<Result<(), Aborted>>,
+}
+
+impl ServiceRunnerHandle {
+    /// Handle to abort the service main loop
+    pub fn abort_handle(&self) -> &AbortHandle {
+        &self.abort_handle
+    }
+
+    /// Wait for the service main loop to finish
+    /// An aborted service counts as finished, it fails only if the service panicked.
+    pub async fn await_completion(self) -> Result<(), JoinError> {
+        self.join_handle.await.map(|_aborted| ())
+    }
 }
 
 impl<S: ServiceCore> ServiceRunner<S> {
     /// Spawn the service main loop and handle it lifecycle
     /// Return a handle to abort execution manually
-    #[instrument(skip(self), fields(service_id=S::SERVICE_ID))]
     pub fn run(self) -> AbortHandle {
+        self.spawn().abort_handle
+    }
+
+    /// Spawn the service main loop and handle it lifecycle
+    /// Return a handle to abort execution manually or wait for the service to finish
+    #[instrument(
+        name = "run",
+        skip(self),
+        fields(service_id = S::SERVICE_ID, buffer_size = S::SERVICE_RELAY_BUFFER_SIZE)
+    )]
+    pub fn spawn(self) -> ServiceRunnerHandle {
         let ServiceRunner {
             service_state,
             state_handle,
-            ..
+            abort_handle,
+            abort_registration,
         } = self;
 
         let runtime = service_state.overwatch_handle.runtime().clone();
+        let spawn_hook = service_state.overwatch_handle.spawn_hook().cloned();
         let service = S::init(service_state);
-        let (runner, abortable_handle) = abortable(service.run());
+        let service_loop: ServiceFuture = match spawn_hook {
+            Some(spawn_hook) => spawn_hook.wrap(S::SERVICE_ID, service.run()),
+            None => service.run(),
+        };
+        let runner = Abortable::new(service_loop, abort_registration);
 
-        runtime.spawn(runner);
-        runtime.spawn(state_handle.run());
+        let join_handle = runtime.spawn(runner);
+        // state watchers read the state channel directly, so the task is only needed to feed
+        // an operator that actually does something
+        if !S::StateOperator::NOOP {
+            runtime.spawn(state_handle.run());
+        }
 
         // TODO: Handle service lifecycle
         // TODO: this handle should not scape this scope, it should actually be handled in the lifecycle part mentioned above
-        abortable_handle
+        ServiceRunnerHandle {
+            abort_handle,
+            join_handle,
+        }
     }
 }
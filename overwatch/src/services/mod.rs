@@ -1,5 +1,12 @@
+pub mod bridge;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+#[cfg(feature = "eventbus")]
+pub mod eventbus;
 pub mod handle;
 pub mod life_cycle;
+#[cfg(feature = "testing")]
+pub mod record;
 pub mod relay;
 pub mod settings;
 pub mod state;
@@ -13,6 +20,7 @@ use tokio::runtime;
 
 // internal
 use crate::services::relay::RelayError;
+use crate::services::settings::SettingsLayer;
 use crate::services::state::StateOperator;
 use handle::ServiceStateHandle;
 use relay::RelayMessage;
@@ -29,6 +37,9 @@ pub trait ServiceData {
     const SERVICE_ID: ServiceId;
     /// Service relay buffer size
     const SERVICE_RELAY_BUFFER_SIZE: usize = 16;
+    /// Whether the application can not run without this service
+    /// When starting in best-effort mode, only critical services failing to start abort startup
+    const CRITICAL: bool = true;
     /// Service settings object
     type Settings: Clone;
     /// Service state object
@@ -37,6 +48,11 @@ pub trait ServiceData {
     type StateOperator: StateOperator<StateInput = Self::State> + Clone;
     /// Service messages that the service itself understands and can react to
     type Message: RelayMessage + Debug + Send + Sync;
+
+    /// Settings pipeline, applied in order to every settings update before the service sees it
+    fn settings_layers() -> Vec<Box<dyn SettingsLayer<Self::Settings>>> {
+        Vec::new()
+    }
 }
 
 /// Main trait for Services initialization and main loop hook
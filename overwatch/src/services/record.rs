@@ -0,0 +1,135 @@
+// std
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+// crates
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tracing::error;
+// internal
+use crate::services::relay::{InboundRelay, OutboundRelay, RelayError};
+
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("invalid recorded message: {0}")]
+    Decode(#[from] serde_json::Error),
+    #[error(transparent)]
+    Relay(RelayError),
+}
+
+/// Message received by a recorded [`InboundRelay`], as stored in the recording
+/// Recordings are json lines files, one recorded message per line.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecordedMessage<M> {
+    /// Time elapsed since the recording started when the message was received
+    pub elapsed: Duration,
+    pub message: M,
+}
+
+/// Type erased [`MessageRecorder`], as registered for a service
+pub type AnyRecorder = Arc<dyn Any + Send + Sync>;
+
+/// Records every message received by an [`InboundRelay`] into a file
+/// Messages are encoded on receive and written from a background thread, so receiving never
+/// waits on the file: a recorded message lands in the file shortly after it is received.
+/// Clones append to the same recording, which is closed once every clone is dropped.
+pub struct MessageRecorder<M> {
+    lines: UnboundedSender<String>,
+    encode: fn(&RecordedMessage<&M>) -> serde_json::Result<String>,
+    started_at: Instant,
+}
+
+impl<M: Serialize> MessageRecorder<M> {
+    /// Record into the file at `path`, overwriting it
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut recording = BufWriter::new(File::create(path)?);
+        let (lines, mut receiver) = unbounded_channel::<String>();
+        thread::Builder::new()
+            .name("overwatch-recorder".to_string())
+            .spawn(move || {
+                while let Some(line) = receiver.blocking_recv() {
+                    if let Err(e) = writeln!(recording, "{line}").and_then(|_| recording.flush()) {
+                        error!(error=?e, "Error recording relay message");
+                    }
+                }
+            })?;
+        Ok(Self {
+            lines,
+            encode: |recorded| serde_json::to_string(recorded),
+            started_at: Instant::now(),
+        })
+    }
+}
+
+impl<M> MessageRecorder<M> {
+    /// Queue a received message for writing
+    /// Recording failures are logged, they do not prevent the message from being received.
+    pub fn record(&self, message: &M) {
+        let recorded = RecordedMessage {
+            elapsed: self.started_at.elapsed(),
+            message,
+        };
+        match (self.encode)(&recorded) {
+            Ok(line) => {
+                if self.lines.send(line).is_err() {
+                    error!("Error recording relay message, the recording is closed");
+                }
+            }
+            Err(e) => error!(error=?e, "Error encoding relay message"),
+        }
+    }
+}
+
+impl<M> Clone for MessageRecorder<M> {
+    fn clone(&self) -> Self {
+        Self {
+            lines: self.lines.clone(),
+            encode: self.encode,
+            started_at: self.started_at,
+        }
+    }
+}
+
+impl<M> Debug for MessageRecorder<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageRecorder")
+            .field("started_at", &self.started_at)
+            .finish()
+    }
+}
+
+impl<M: Serialize> InboundRelay<M> {
+    /// Record every received message into the file at `path`, overwriting it
+    pub fn record(self, path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(self.with_recorder(MessageRecorder::create(path)?))
+    }
+}
+
+/// Send the messages recorded in the file at `path` through the relay, in the recorded order
+/// Messages are sent back to back, the recorded timings are kept in the file for inspection
+/// but not reproduced, so replays are deterministic. Returns the amount of replayed messages.
+pub async fn replay<M: DeserializeOwned>(
+    path: impl AsRef<Path>,
+    relay: &OutboundRelay<M>,
+) -> Result<usize, ReplayError> {
+    let recording = BufReader::new(File::open(path)?);
+    let mut replayed = 0;
+    for line in recording.lines() {
+        let recorded: RecordedMessage<M> = serde_json::from_str(&line?)?;
+        relay
+            .send(recorded.message)
+            .await
+            .map_err(|(e, _)| ReplayError::Relay(e))?;
+        replayed += 1;
+    }
+    Ok(replayed)
+}
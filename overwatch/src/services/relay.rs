@@ -1,15 +1,28 @@
 // std
 use std::any::Any;
+use std::collections::{HashSet, VecDeque};
 use std::fmt::Debug;
+use std::hash::Hash;
 use std::marker::PhantomData;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 // crates
+use futures::Sink;
 use thiserror::Error;
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
-use tokio::sync::oneshot;
-use tracing::{error, instrument};
+use tokio::sync::{oneshot, Notify};
+use tokio::time::{sleep, timeout_at};
+use tracing::{error, instrument, warn};
 // internal
 use crate::overwatch::commands::{OverwatchCommand, RelayCommand, ReplyChannel};
 use crate::overwatch::handle::OverwatchHandle;
+#[cfg(feature = "chaos")]
+use crate::services::chaos::FaultInjector;
+#[cfg(feature = "testing")]
+use crate::services::record::MessageRecorder;
 use crate::services::{ServiceCore, ServiceId};
 
 #[derive(Error, Debug)]
@@ -18,6 +31,14 @@ pub enum RelayError {
     InvalidRequest { to: ServiceId },
     #[error("couldn't relay message")]
     Send,
+    #[error("service relay is full")]
+    Full,
+    #[error("service {service_id} relay buffer size {buffer_size} is over the {limit} limit")]
+    BufferTooLarge {
+        service_id: ServiceId,
+        buffer_size: usize,
+        limit: usize,
+    },
     #[error("relay is already connected")]
     AlreadyConnected,
     #[error("service relay is disconnected")]
@@ -52,13 +73,263 @@ pub trait RelayMessage: 'static {}
 #[derive(Debug)]
 pub struct InboundRelay<M> {
     receiver: Receiver<M>,
+    budget: Arc<BufferBudget>,
+    #[cfg(feature = "chaos")]
+    faults: Option<FaultInjector>,
+    /// Message held back by an injected delay, with the time it is delivered at
+    /// Kept here so a receive cancelled while waiting does not lose it
+    #[cfg(feature = "chaos")]
+    delayed: Option<(M, tokio::time::Instant)>,
+    #[cfg(feature = "testing")]
+    recorder: Option<MessageRecorder<M>>,
     _stats: (), // placeholder
 }
 
 /// Channel sender of a relay connection
+#[derive(Debug)]
 pub struct OutboundRelay<M> {
     sender: Sender<M>,
-    _stats: (), // placeholder
+    throughput: Arc<Throughput>,
+    budget: Arc<BufferBudget>,
+}
+
+/// Accounting of the approximate bytes buffered across the relays sharing it
+/// Every buffered message is estimated as `size_of::<M>()`, heap data owned by the message is not
+/// taken into account. When a cap is set, sends wait (or fail as full, for the non waiting
+/// variants) until enough buffered messages are received.
+#[derive(Debug, Default)]
+pub struct BufferBudget {
+    buffered: AtomicUsize,
+    cap: Option<usize>,
+    released: Notify,
+}
+
+impl BufferBudget {
+    /// Budget that applies backpressure once `cap` bytes are buffered
+    pub fn with_cap(cap: usize) -> Self {
+        Self {
+            cap: Some(cap),
+            ..Default::default()
+        }
+    }
+
+    /// Approximate bytes currently buffered
+    pub fn buffered_bytes(&self) -> usize {
+        self.buffered.load(Ordering::Acquire)
+    }
+
+    fn try_acquire(&self, bytes: usize) -> bool {
+        match self.cap {
+            None => {
+                self.buffered.fetch_add(bytes, Ordering::AcqRel);
+                true
+            }
+            Some(cap) => self
+                .buffered
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |buffered| {
+                    // a single message bigger than the cap is let through on an empty budget
+                    if buffered == 0 || buffered + bytes <= cap {
+                        Some(buffered + bytes)
+                    } else {
+                        None
+                    }
+                })
+                .is_ok(),
+        }
+    }
+
+    async fn acquire(&self, bytes: usize) {
+        loop {
+            // created before checking, so a release in between is not missed
+            let released = self.released.notified();
+            if self.try_acquire(bytes) {
+                return;
+            }
+            released.await;
+        }
+    }
+
+    fn release(&self, bytes: usize) {
+        self.buffered.fetch_sub(bytes, Ordering::AcqRel);
+        self.released.notify_waiters();
+    }
+}
+
+/// How often [`Throughput`] folds the sent messages into its moving average
+pub const THROUGHPUT_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Rolling estimation of the messages per second sent through a relay
+/// Sent messages are counted and folded into an exponentially weighted moving average once
+/// per [`THROUGHPUT_SAMPLE_INTERVAL`], lazily on the next send or read, so no task has to
+/// drive it. It is shared by every clone of the [`OutboundRelay`].
+#[derive(Debug)]
+pub struct Throughput {
+    sent: AtomicU64,
+    /// Bits of the `f64` moving average
+    rate: AtomicU64,
+    /// End of the last interval folded into the moving average
+    sampled_at: Mutex<Instant>,
+}
+
+impl Default for Throughput {
+    fn default() -> Self {
+        Self {
+            sent: AtomicU64::default(),
+            rate: AtomicU64::default(),
+            sampled_at: Mutex::new(Instant::now()),
+        }
+    }
+}
+
+impl Throughput {
+    /// Weight of the newest sample in the moving average
+    const ALPHA: f64 = 0.3;
+
+    fn record(&self) {
+        self.sent.fetch_add(1, Ordering::Relaxed);
+        self.catch_up(Instant::now());
+    }
+
+    /// Fold the messages sent since the previous sample into the moving average
+    /// A zero `elapsed` is ignored, the messages are kept for the next sample.
+    pub fn sample(&self, elapsed: Duration) {
+        if elapsed.is_zero() {
+            return;
+        }
+        let sent = self.sent.swap(0, Ordering::Relaxed) as f64;
+        let current = sent / elapsed.as_secs_f64();
+        let rate = f64::from_bits(self.rate.load(Ordering::Relaxed));
+        let rate = Self::ALPHA * current + (1.0 - Self::ALPHA) * rate;
+        self.rate.store(rate.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Latest moving average of sent messages per second
+    pub fn messages_per_second(&self) -> f64 {
+        self.catch_up(Instant::now());
+        f64::from_bits(self.rate.load(Ordering::Relaxed))
+    }
+
+    /// Sample every interval completed by `now`
+    /// The messages sent since the last sample all count towards the first interval, the ones
+    /// that follow had none and just decay the average.
+    fn catch_up(&self, now: Instant) {
+        // whoever holds the lock is already sampling
+        let mut sampled_at = match self.sampled_at.try_lock() {
+            Ok(sampled_at) => sampled_at,
+            Err(_) => return,
+        };
+        let intervals = (now.saturating_duration_since(*sampled_at).as_nanos()
+            / THROUGHPUT_SAMPLE_INTERVAL.as_nanos()) as u32;
+        if intervals == 0 {
+            return;
+        }
+        self.sample(THROUGHPUT_SAMPLE_INTERVAL);
+        let rate = f64::from_bits(self.rate.load(Ordering::Relaxed));
+        let rate = rate * (1.0 - Self::ALPHA).powi(intervals.saturating_sub(1) as i32);
+        self.rate.store(rate.to_bits(), Ordering::Relaxed);
+        *sampled_at += THROUGHPUT_SAMPLE_INTERVAL * intervals;
+    }
+}
+
+/// Delay between consecutive send attempts of [`OutboundRelay::send_with_retry`]
+#[derive(Debug, Clone, Copy)]
+pub enum Backoff {
+    /// Wait always the same amount of time
+    Constant(Duration),
+    /// Double the wait time after each attempt, up to `max`
+    Exponential { initial: Duration, max: Duration },
+}
+
+impl Backoff {
+    fn delay(&self, attempt: u32) -> Duration {
+        match self {
+            Backoff::Constant(delay) => *delay,
+            Backoff::Exponential { initial, max } => initial
+                .saturating_mul(2u32.saturating_pow(attempt))
+                .min(*max),
+        }
+    }
+}
+
+/// How [`OutboundRelay::send_with_retry`] retries sending to a full relay
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total amount of send attempts
+    pub max_attempts: u32,
+    pub backoff: Backoff,
+}
+
+/// Bounds for the service relays buffer sizes
+/// Services over the `soft` limit start with a warning, services over the `hard` limit fail to
+/// start. They catch accidentally huge buffers, like a `SERVICE_RELAY_BUFFER_SIZE` typo.
+#[derive(Debug, Clone, Copy)]
+pub struct RelayBufferLimits {
+    pub soft: usize,
+    pub hard: usize,
+}
+
+impl Default for RelayBufferLimits {
+    fn default() -> Self {
+        Self {
+            soft: 10_000,
+            hard: 1_000_000,
+        }
+    }
+}
+
+impl RelayBufferLimits {
+    /// Check a service relay buffer size against the limits
+    pub fn check(&self, service_id: ServiceId, buffer_size: usize) -> Result<(), RelayError> {
+        if buffer_size > self.hard {
+            return Err(RelayError::BufferTooLarge {
+                service_id,
+                buffer_size,
+                limit: self.hard,
+            });
+        }
+        if buffer_size > self.soft {
+            warn!(
+                service_id,
+                buffer_size,
+                limit = self.soft,
+                "Service relay buffer size is over the soft limit"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Bounds of a batch received with [`InboundRelay::recv_batch`]
+/// Meant to be part of the service settings, so batching can be tuned without code changes
+#[derive(Debug, Clone, Copy)]
+pub struct BatchWindow {
+    /// Maximum number of messages in a batch
+    pub max_messages: usize,
+    /// Maximum time to wait for a batch to fill up since its first message arrived
+    pub window: Duration,
+}
+
+/// Window of recently received messages a [`DedupInboundRelay`] checks duplicates against
+#[derive(Debug, Clone, Copy)]
+pub struct DedupWindow {
+    /// Maximum number of messages kept, 0 disables deduplication
+    pub max_entries: usize,
+    /// How long a message is kept
+    pub ttl: Duration,
+}
+
+/// Channel receiver that drops messages already received within a [`DedupWindow`]
+/// Messages are compared by equality, a copy of each message is kept while in the window.
+/// Duplicates are dropped and counted.
+#[derive(Debug)]
+pub struct DedupInboundRelay<M> {
+    inbound_relay: InboundRelay<M>,
+    window: DedupWindow,
+    /// Messages in the window, oldest first
+    seen: VecDeque<(M, Instant)>,
+    /// Same messages as `seen`, for lookups
+    seen_set: HashSet<M>,
+    deduped: usize,
 }
 
 #[derive(Debug)]
@@ -80,7 +351,8 @@ impl<M> Clone for OutboundRelay<M> {
     fn clone(&self) -> Self {
         Self {
             sender: self.sender.clone(),
-            _stats: (),
+            throughput: self.throughput.clone(),
+            budget: self.budget.clone(),
         }
     }
 }
@@ -88,30 +360,230 @@ impl<M> Clone for OutboundRelay<M> {
 // TODO: make buffer_size const?
 /// Relay channel builder
 pub fn relay<M>(buffer_size: usize) -> (InboundRelay<M>, OutboundRelay<M>) {
+    relay_with_budget(buffer_size, Arc::new(BufferBudget::default()))
+}
+
+/// Relay channel builder accounting its buffered messages in a shared [`BufferBudget`]
+pub fn relay_with_budget<M>(
+    buffer_size: usize,
+    budget: Arc<BufferBudget>,
+) -> (InboundRelay<M>, OutboundRelay<M>) {
     let (sender, receiver) = channel(buffer_size);
     (
         InboundRelay {
             receiver,
+            budget: budget.clone(),
+            #[cfg(feature = "chaos")]
+            faults: None,
+            #[cfg(feature = "chaos")]
+            delayed: None,
+            #[cfg(feature = "testing")]
+            recorder: None,
             _stats: (),
         },
-        OutboundRelay { sender, _stats: () },
+        OutboundRelay {
+            sender,
+            throughput: Arc::new(Throughput::default()),
+            budget,
+        },
     )
 }
 
 impl<M> InboundRelay<M> {
     /// Receive a message from the relay connections
+    /// Cancel safe: if the returned future is dropped before completing no message is lost, the
+    /// next call receives it. This holds with injected faults too, a delayed message waits in
+    /// the relay until the next call.
     pub async fn recv(&mut self) -> Option<M> {
-        self.receiver.recv().await
+        #[cfg(feature = "chaos")]
+        let message = match self.faults.clone() {
+            Some(faults) => self.recv_with_faults(faults).await?,
+            None => self.recv_buffered().await?,
+        };
+        #[cfg(not(feature = "chaos"))]
+        let message = self.recv_buffered().await?;
+        #[cfg(feature = "testing")]
+        if let Some(recorder) = &self.recorder {
+            recorder.record(&message);
+        }
+        Some(message)
+    }
+
+    async fn recv_buffered(&mut self) -> Option<M> {
+        let message = self.receiver.recv().await?;
+        self.budget.release(size_of::<M>());
+        Some(message)
+    }
+
+    /// Inject the provided faults on every received message, see [`FaultInjector`]
+    #[cfg(feature = "chaos")]
+    pub fn with_faults(mut self, faults: FaultInjector) -> Self {
+        self.faults = Some(faults);
+        self
+    }
+
+    /// Record every received message with the provided recorder, see [`MessageRecorder`]
+    #[cfg(feature = "testing")]
+    pub fn with_recorder(mut self, recorder: MessageRecorder<M>) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    #[cfg(feature = "chaos")]
+    async fn recv_with_faults(&mut self, faults: FaultInjector) -> Option<M> {
+        let deliver_at = loop {
+            if let Some((_, deliver_at)) = &self.delayed {
+                break *deliver_at;
+            }
+            let message = self.recv_buffered().await?;
+            match faults.delivery_delay() {
+                Some(delay) => {
+                    self.delayed = Some((message, tokio::time::Instant::now() + delay));
+                }
+                // dropped right away instead of being held while waiting for the next one
+                None => drop(message),
+            }
+        };
+        if deliver_at > tokio::time::Instant::now() {
+            tokio::time::sleep_until(deliver_at).await;
+        }
+        self.delayed.take().map(|(message, _)| message)
+    }
+
+    /// Receive a batch of messages
+    /// Waits for a first message, then gathers messages until the batch is full or the window
+    /// elapses, whatever happens first. Returns `None` once the relay is closed and drained.
+    pub async fn recv_batch(&mut self, batch: BatchWindow) -> Option<Vec<M>> {
+        let first = self.recv().await?;
+        let flush_at = tokio::time::Instant::now() + batch.window;
+        let mut messages = Vec::with_capacity(batch.max_messages);
+        messages.push(first);
+        while messages.len() < batch.max_messages {
+            match timeout_at(flush_at, self.recv()).await {
+                Ok(Some(message)) => messages.push(message),
+                // window elapsed or relay closed, flush what was gathered
+                Ok(None) | Err(_) => break,
+            }
+        }
+        Some(messages)
+    }
+
+    /// Drop duplicated messages received within the given window
+    pub fn dedup(self, window: DedupWindow) -> DedupInboundRelay<M> {
+        DedupInboundRelay {
+            inbound_relay: self,
+            window,
+            seen: VecDeque::new(),
+            seen_set: HashSet::new(),
+            deduped: 0,
+        }
+    }
+}
+
+impl<M> Drop for InboundRelay<M> {
+    fn drop(&mut self) {
+        // messages left in the buffer are dropped with it, give their budget back
+        self.receiver.close();
+        while self.receiver.try_recv().is_ok() {
+            self.budget.release(size_of::<M>());
+        }
+    }
+}
+
+impl<M: Hash + Eq + Clone> DedupInboundRelay<M> {
+    /// Receive the next message not seen within the window
+    pub async fn recv(&mut self) -> Option<M> {
+        loop {
+            let message = self.inbound_relay.recv().await?;
+            if self.window.max_entries == 0 {
+                return Some(message);
+            }
+            let now = Instant::now();
+            self.evict_expired(now);
+            if self.seen_set.contains(&message) {
+                self.deduped += 1;
+                continue;
+            }
+            if self.seen.len() >= self.window.max_entries {
+                self.pop_oldest();
+            }
+            self.seen_set.insert(message.clone());
+            self.seen.push_back((message.clone(), now));
+            return Some(message);
+        }
+    }
+
+    /// Amount of duplicated messages dropped so far
+    pub fn deduped(&self) -> usize {
+        self.deduped
+    }
+
+    fn evict_expired(&mut self, now: Instant) {
+        while matches!(
+            self.seen.front(),
+            Some((_, received_at)) if now.duration_since(*received_at) > self.window.ttl
+        ) {
+            self.pop_oldest();
+        }
+    }
+
+    fn pop_oldest(&mut self) {
+        if let Some((message, _)) = self.seen.pop_front() {
+            self.seen_set.remove(&message);
+        }
     }
 }
 
 impl<M> OutboundRelay<M> {
     /// Send a message to the relay connection
+    /// Cancel safe: the buffer slot and the budget are only taken once both are available, so
+    /// a send dropped while waiting for either leaves nothing behind.
     pub async fn send(&self, message: M) -> Result<(), (RelayError, M)> {
-        self.sender
-            .send(message)
-            .await
-            .map_err(|e| (RelayError::Send, e.0))
+        let permit = match self.sender.reserve().await {
+            Ok(permit) => permit,
+            Err(_) => return Err((RelayError::Send, message)),
+        };
+        self.budget.acquire(size_of::<M>()).await;
+        permit.send(message);
+        self.throughput.record();
+        Ok(())
+    }
+
+    /// Send a message to the relay connection, retrying while the relay buffer is full
+    ///
+    /// Each attempt that finds the buffer full waits as the policy backoff states before trying
+    /// again. Fails right away with [`RelayError::Send`] if the relay is closed, like
+    /// [`Self::send`], and with [`RelayError::Full`] once the policy attempts are exhausted.
+    pub async fn send_with_retry(
+        &self,
+        mut message: M,
+        policy: RetryPolicy,
+    ) -> Result<(), (RelayError, M)> {
+        let mut attempt = 0;
+        loop {
+            let sent = if self.budget.try_acquire(size_of::<M>()) {
+                self.sender
+                    .try_send(message)
+                    .inspect_err(|_| self.budget.release(size_of::<M>()))
+            } else {
+                Err(TrySendError::Full(message))
+            };
+            match sent {
+                Ok(()) => {
+                    self.throughput.record();
+                    return Ok(());
+                }
+                Err(TrySendError::Closed(m)) => return Err((RelayError::Send, m)),
+                Err(TrySendError::Full(m)) => {
+                    attempt += 1;
+                    if attempt >= policy.max_attempts {
+                        return Err((RelayError::Full, m));
+                    }
+                    sleep(policy.backoff.delay(attempt - 1)).await;
+                    message = m;
+                }
+            }
+        }
     }
 
     /// Send a message to the relay connection in a blocking fashion.
@@ -126,9 +598,80 @@ impl<M> OutboundRelay<M> {
     ///
     /// # Exa
     pub fn blocking_send(&self, message: M) -> Result<(), (RelayError, M)> {
-        self.sender
-            .blocking_send(message)
-            .map_err(|e| (RelayError::Send, e.0))
+        futures::executor::block_on(self.budget.acquire(size_of::<M>()));
+        self.sender.blocking_send(message).map_err(|e| {
+            self.budget.release(size_of::<M>());
+            (RelayError::Send, e.0)
+        })?;
+        self.throughput.record();
+        Ok(())
+    }
+
+    /// Throughput of the relay, shared by all its clones
+    pub fn throughput(&self) -> Arc<Throughput> {
+        self.throughput.clone()
+    }
+
+    /// Wait until the receiving end of the relay is closed
+    /// Resolves once the [`InboundRelay`] is dropped, usually because the receiving service
+    /// stopped, so producers can stop working for it. See [`Sender::closed`].
+    pub async fn closed(&self) {
+        self.sender.closed().await
+    }
+
+    /// Whether the receiving end of the relay is closed
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
+    /// Turn the relay into a [`Sink`], so producers can `forward` streams into it
+    /// The sink is ready once the previous message is sent, so a full relay buffer backpressures
+    /// the producer. Fails with the error of the first message that cannot be sent.
+    pub fn into_sink(self) -> impl Sink<M, Error = RelayError> {
+        futures::sink::unfold(self, |relay, message| async move {
+            relay.send(message).await.map_err(|(e, _)| e)?;
+            Ok(relay)
+        })
+    }
+}
+
+impl<M: Send + 'static> OutboundRelay<M> {
+    /// Relay accepting `N` messages that are converted and forwarded into this relay
+    /// See [`Self::filter_map`].
+    pub fn map<N, F>(self, transform: F) -> OutboundRelay<N>
+    where
+        N: Send + 'static,
+        F: Fn(N) -> M + Send + 'static,
+    {
+        self.filter_map(move |message| Some(transform(message)))
+    }
+
+    /// Relay accepting `N` messages that are converted and forwarded into this relay, messages
+    /// the transformation returns `None` for are dropped
+    ///
+    /// Forwarding is done by a task spawned in the current runtime, so it must be called within
+    /// a runtime context. The returned relay buffers a single message, backpressure from this
+    /// relay is propagated to its senders. The task finishes once every clone of the returned
+    /// relay is dropped or this relay gets disconnected.
+    pub fn filter_map<N, F>(self, transform: F) -> OutboundRelay<N>
+    where
+        N: Send + 'static,
+        F: Fn(N) -> Option<M> + Send + 'static,
+    {
+        let (mut inbound_relay, outbound_relay) = relay::<N>(1);
+        tokio::spawn(async move {
+            while let Some(message) = inbound_relay.recv().await {
+                let message = match transform(message) {
+                    Some(message) => message,
+                    None => continue,
+                };
+                if self.send(message).await.is_err() {
+                    error!("Error forwarding mapped message, relay is disconnected");
+                    break;
+                }
+            }
+        });
+        outbound_relay
     }
 }
 
@@ -174,3 +717,181 @@ impl<S: ServiceCore> Relay<S> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::services::relay::{
+        relay, relay_with_budget, Backoff, BatchWindow, BufferBudget, RelayError, RetryPolicy,
+        Throughput, THROUGHPUT_SAMPLE_INTERVAL,
+    };
+    use std::mem::size_of;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::time::{sleep, timeout};
+
+    const RETRY_POLICY: RetryPolicy = RetryPolicy {
+        max_attempts: 10,
+        backoff: Backoff::Constant(Duration::from_millis(20)),
+    };
+
+    #[tokio::test]
+    async fn send_with_retry_waits_for_free_buffer() {
+        let (mut inbound_relay, outbound_relay) = relay::<usize>(1);
+        outbound_relay.send(0).await.expect("Buffer to have room");
+        let consumer = tokio::spawn(async move {
+            sleep(Duration::from_millis(50)).await;
+            let first = inbound_relay.recv().await;
+            (first, inbound_relay)
+        });
+
+        outbound_relay
+            .send_with_retry(1, RETRY_POLICY)
+            .await
+            .expect("Message to be sent once the buffer frees up");
+        let (first, mut inbound_relay) = consumer.await.expect("Consumer to finish");
+        assert_eq!(first, Some(0));
+        assert_eq!(inbound_relay.recv().await, Some(1));
+    }
+
+    #[tokio::test]
+    async fn throughput_converges_to_sent_rate() {
+        let (mut inbound_relay, outbound_relay) = relay::<usize>(64);
+        let throughput = outbound_relay.throughput();
+        for _ in 0..20 {
+            for message in 0..50 {
+                outbound_relay
+                    .send(message)
+                    .await
+                    .expect("Buffer to have room");
+            }
+            for _ in 0..50 {
+                inbound_relay.recv().await;
+            }
+            // as if one second passed
+            throughput.sample(Duration::from_secs(1));
+        }
+        assert!((throughput.messages_per_second() - 50.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn throughput_decays_without_a_sampler() {
+        let throughput = Throughput::default();
+        let start = *throughput.sampled_at.lock().unwrap();
+        for _ in 0..10 {
+            throughput.sent.fetch_add(1, Ordering::Relaxed);
+        }
+        throughput.catch_up(start + THROUGHPUT_SAMPLE_INTERVAL);
+        let rate = f64::from_bits(throughput.rate.load(Ordering::Relaxed));
+        assert!((rate - 3.0).abs() < 1e-9);
+        // three idle intervals
+        throughput.catch_up(start + THROUGHPUT_SAMPLE_INTERVAL * 4);
+        let rate = f64::from_bits(throughput.rate.load(Ordering::Relaxed));
+        assert!((rate - 3.0 * 0.7f64.powi(3)).abs() < 1e-9);
+        // nothing left to fold in, a zero interval changes nothing either
+        throughput.sample(Duration::ZERO);
+        assert_eq!(
+            f64::from_bits(throughput.rate.load(Ordering::Relaxed)),
+            rate
+        );
+    }
+
+    #[tokio::test]
+    async fn cancelled_send_does_not_hold_budget() {
+        let budget = Arc::new(BufferBudget::default());
+        let (mut inbound_relay, outbound_relay) = relay_with_budget::<u64>(1, budget.clone());
+        outbound_relay.send(0).await.expect("Buffer to have room");
+        assert!(timeout(Duration::from_millis(50), outbound_relay.send(1))
+            .await
+            .is_err());
+        assert_eq!(inbound_relay.recv().await, Some(0));
+        assert_eq!(budget.buffered_bytes(), 0);
+    }
+
+    #[tokio::test]
+    async fn send_with_retry_gives_up_on_closed_relay() {
+        let (inbound_relay, outbound_relay) = relay::<usize>(1);
+        drop(inbound_relay);
+
+        let result = outbound_relay.send_with_retry(1, RETRY_POLICY).await;
+        assert!(matches!(result, Err((RelayError::Send, 1))));
+    }
+
+    #[tokio::test]
+    async fn buffer_budget_applies_backpressure_across_relays() {
+        let budget = Arc::new(BufferBudget::with_cap(2 * size_of::<u64>()));
+        let (mut first_inbound, first_outbound) = relay_with_budget::<u64>(8, budget.clone());
+        let (mut second_inbound, second_outbound) = relay_with_budget::<u64>(8, budget.clone());
+        first_outbound.send(0).await.expect("Budget to have room");
+        first_outbound.send(1).await.expect("Budget to have room");
+        assert_eq!(budget.buffered_bytes(), 2 * size_of::<u64>());
+
+        // the second relay buffer is empty, but the shared budget is exhausted
+        assert!(timeout(Duration::from_millis(50), second_outbound.send(2))
+            .await
+            .is_err());
+        let result = second_outbound
+            .send_with_retry(
+                2,
+                RetryPolicy {
+                    max_attempts: 1,
+                    backoff: Backoff::Constant(Duration::ZERO),
+                },
+            )
+            .await;
+        assert!(matches!(result, Err((RelayError::Full, 2))));
+
+        let blocked_send = tokio::spawn(async move {
+            second_outbound.send(2).await.expect("Message to be sent");
+        });
+        assert_eq!(first_inbound.recv().await, Some(0));
+        blocked_send
+            .await
+            .expect("Send to resume once budget is freed");
+        assert_eq!(second_inbound.recv().await, Some(2));
+        assert_eq!(budget.buffered_bytes(), size_of::<u64>());
+    }
+
+    #[tokio::test]
+    async fn filter_map_forwards_transformed_messages() {
+        let (mut inbound_relay, outbound_relay) = relay::<usize>(4);
+        let mapped = outbound_relay.filter_map(|message: &'static str| {
+            if message.is_empty() {
+                None
+            } else {
+                Some(message.len())
+            }
+        });
+        for message in ["one", "", "three"] {
+            mapped.send(message).await.expect("Message to be sent");
+        }
+
+        assert_eq!(inbound_relay.recv().await, Some(3));
+        assert_eq!(inbound_relay.recv().await, Some(5));
+        drop(mapped);
+        assert_eq!(inbound_relay.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn recv_batch_flushes_on_size_or_window() {
+        let (mut inbound_relay, outbound_relay) = relay::<usize>(8);
+        let batch = BatchWindow {
+            max_messages: 2,
+            window: Duration::from_millis(50),
+        };
+        for message in 0..3 {
+            outbound_relay
+                .send(message)
+                .await
+                .expect("Message to be sent");
+        }
+
+        assert_eq!(inbound_relay.recv_batch(batch).await, Some(vec![0, 1]));
+        // only one message left, the batch is flushed once the window elapses
+        let started_at = std::time::Instant::now();
+        assert_eq!(inbound_relay.recv_batch(batch).await, Some(vec![2]));
+        assert!(started_at.elapsed() >= batch.window);
+        drop(outbound_relay);
+        assert_eq!(inbound_relay.recv_batch(batch).await, None);
+    }
+}
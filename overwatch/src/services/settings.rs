@@ -1,17 +1,92 @@
 //std
+use std::pin::Pin;
+use std::task::{Context, Poll};
 //crates
+use futures::{Stream, StreamExt};
+use thiserror::Error;
 use tokio::sync::watch::{channel, Receiver, Sender};
+use tokio_stream::wrappers::WatchStream;
 use tracing::{error, instrument};
 //internal
 
+#[derive(Error, Debug, Clone)]
+pub enum SettingsError {
+    #[error("invalid settings: {0}")]
+    Invalid(String),
+}
+
+/// Settings transformation step
+/// Layers are applied in order on each settings update before the new value is published,
+/// they can validate, normalize or enrich the incoming settings.
+pub trait SettingsLayer<S>: Send + Sync {
+    fn apply(&self, settings: S) -> Result<S, SettingsError>;
+}
+
+/// Layer rejecting settings that do not pass the validation function
+pub struct ValidationLayer<F> {
+    validate: F,
+}
+
+impl<F> ValidationLayer<F> {
+    pub fn new(validate: F) -> Self {
+        Self { validate }
+    }
+}
+
+impl<S, F> SettingsLayer<S> for ValidationLayer<F>
+where
+    F: Fn(&S) -> Result<(), String> + Send + Sync,
+{
+    fn apply(&self, settings: S) -> Result<S, SettingsError> {
+        (self.validate)(&settings).map_err(SettingsError::Invalid)?;
+        Ok(settings)
+    }
+}
+
+/// Layer bounding settings into the `[min, max]` range
+pub struct ClampLayer<S> {
+    min: S,
+    max: S,
+}
+
+impl<S> ClampLayer<S> {
+    pub fn new(min: S, max: S) -> Self {
+        Self { min, max }
+    }
+}
+
+impl<S> SettingsLayer<S> for ClampLayer<S>
+where
+    S: PartialOrd + Clone + Send + Sync,
+{
+    fn apply(&self, settings: S) -> Result<S, SettingsError> {
+        let settings = if settings < self.min {
+            self.min.clone()
+        } else {
+            settings
+        };
+        Ok(if settings > self.max {
+            self.max.clone()
+        } else {
+            settings
+        })
+    }
+}
+
 /// Wrapper around [`tokio::sync::watch::Receiver`]
+/// It is also a [`Stream`] of the settings, yielding the current ones first and then the latest
+/// ones after every change. Changes happening before the stream is polled again are coalesced.
 pub struct SettingsNotifier<S> {
     notifier_channel: Receiver<S>,
+    updates: Option<WatchStream<S>>,
 }
 
 impl<S: Clone> SettingsNotifier<S> {
     pub fn new(notifier_channel: Receiver<S>) -> Self {
-        Self { notifier_channel }
+        Self {
+            notifier_channel,
+            updates: None,
+        }
     }
 
     /// Get latest settings, it is guaranteed that at least an initial value is present
@@ -27,22 +102,72 @@ impl<S: Clone> SettingsNotifier<S> {
     }
 }
 
+impl<S> Stream for SettingsNotifier<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    type Item = S;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        this.updates
+            .get_or_insert_with(|| WatchStream::new(this.notifier_channel.clone()))
+            .poll_next_unpin(cx)
+    }
+}
+
 /// Settings update notification sender
 pub struct SettingsUpdater<S> {
     sender: Sender<S>,
     receiver: Receiver<S>,
+    layers: Vec<Box<dyn SettingsLayer<S>>>,
 }
 
 impl<S> SettingsUpdater<S> {
     pub fn new(settings: S) -> Self {
         let (sender, receiver) = channel(settings);
 
-        Self { sender, receiver }
+        Self {
+            sender,
+            receiver,
+            layers: Vec::new(),
+        }
+    }
+
+    /// Add a layer at the end of the settings pipeline
+    pub fn with_layer<L: SettingsLayer<S> + 'static>(mut self, layer: L) -> Self {
+        self.layers.push(Box::new(layer));
+        self
     }
 
-    /// Send a new settings update notification to the watcher end
+    /// Add several layers at the end of the settings pipeline
+    pub fn with_layers(
+        mut self,
+        layers: impl IntoIterator<Item = Box<dyn SettingsLayer<S>>>,
+    ) -> Self {
+        self.layers.extend(layers);
+        self
+    }
+
+    /// Run the settings through the pipeline and send the resulting settings update notification
+    /// to the watcher end. Settings rejected by any layer are not published.
     #[instrument(skip_all)]
-    pub fn update(&self, settings: S) {
+    pub fn update(&self, settings: S) -> Result<(), SettingsError> {
+        let settings = self.prepare(settings)?;
+        self.publish(settings);
+        Ok(())
+    }
+
+    /// Run the settings through the pipeline without publishing them
+    /// First phase of an update, the resulting settings are meant to be [`Self::publish`]ed.
+    pub fn prepare(&self, settings: S) -> Result<S, SettingsError> {
+        self.layers
+            .iter()
+            .try_fold(settings, |settings, layer| layer.apply(settings))
+    }
+
+    /// Send already prepared settings update notification to the watcher end
+    pub fn publish(&self, settings: S) {
         self.sender.send(settings).unwrap_or_else(|_e| {
             error!("Error sending settings update for service");
         });
@@ -52,13 +177,15 @@ impl<S> SettingsUpdater<S> {
     pub fn notifier(&self) -> SettingsNotifier<S> {
         SettingsNotifier {
             notifier_channel: self.receiver.clone(),
+            updates: None,
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::services::settings::SettingsUpdater;
+    use crate::services::settings::{ClampLayer, SettingsUpdater, ValidationLayer};
+    use futures::StreamExt;
     use std::collections::HashSet;
     use std::time::Duration;
     use tokio::time::sleep;
@@ -80,11 +207,56 @@ mod test {
         }));
         sleep(Duration::from_millis(100)).await;
         for v in &values[1..] {
-            updater.update(*v);
+            updater
+                .update(*v)
+                .expect("Settings without layers to be valid");
             sleep(Duration::from_millis(100)).await;
         }
         // all values updates have been seen
         let success: Result<bool, _> = handle.await.unwrap();
         assert!(success.unwrap());
     }
+
+    #[tokio::test]
+    async fn settings_notifier_streams_latest_settings() {
+        let updater = SettingsUpdater::new(0usize);
+        let mut notifier = updater.notifier();
+        assert_eq!(notifier.next().await, Some(0));
+
+        updater
+            .update(1)
+            .expect("Settings without layers to be valid");
+        assert_eq!(notifier.next().await, Some(1));
+
+        // rapid changes are coalesced into the latest one
+        updater
+            .update(2)
+            .expect("Settings without layers to be valid");
+        updater
+            .update(3)
+            .expect("Settings without layers to be valid");
+        assert_eq!(notifier.next().await, Some(3));
+        assert!(timeout(Duration::from_millis(50), notifier.next())
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn settings_layers_clamp_then_validate() {
+        let updater = SettingsUpdater::new(10usize)
+            .with_layer(ClampLayer::new(0, 100))
+            .with_layer(ValidationLayer::new(|settings: &usize| {
+                if settings.is_multiple_of(2) {
+                    Ok(())
+                } else {
+                    Err(format!("{settings} is not even"))
+                }
+            }));
+        let mut notifier = updater.notifier();
+
+        updater.update(1000).expect("Clamped settings to be valid");
+        assert_eq!(notifier.get_updated_settings(), 100);
+        assert!(updater.update(13).is_err());
+        assert_eq!(notifier.get_updated_settings(), 100);
+    }
 }
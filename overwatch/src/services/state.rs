@@ -1,11 +1,15 @@
 // std
+use std::any::Any;
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 // crates
 use async_trait::async_trait;
 use futures::StreamExt;
 use tokio::sync::watch::{channel, Receiver, Ref, Sender};
+use tokio::task::yield_now;
+use tokio::time::{timeout_at, Instant};
 use tokio_stream::wrappers::WatchStream;
 use tracing::error;
 // internal
@@ -28,10 +32,28 @@ pub trait ServiceState: Send + Sync + 'static {
 pub trait StateOperator: Send {
     /// The type of state that the operator can handle
     type StateInput: ServiceState;
+    /// Whether the operator does nothing upon state updates
+    /// If so, there is no need to run a task feeding it the incoming states
+    const NOOP: bool = false;
     /// Operator initialization method. Can be implemented over some subset of settings
     fn from_settings<Settings>(settings: Settings) -> Self;
     /// Asynchronously perform an operation for a given state
     async fn run(&mut self, state: Self::StateInput);
+    /// Interval between checkpoints of the state, `None` (the default) disables checkpointing
+    fn checkpoint_interval(&self) -> Option<Duration> {
+        None
+    }
+    /// Asynchronously checkpoint the current state
+    /// Called every [`StateOperator::checkpoint_interval`], whether the state changed or not.
+    async fn checkpoint(&mut self, _state: Self::StateInput) {}
+    /// Amount of state updates handled before yielding back to the runtime, 0 (the default)
+    /// never yields
+    /// Operators doing heavy work without awaiting can hold a constrained runtime for as long as
+    /// the state keeps changing, yielding lets the service task make progress meanwhile. A higher
+    /// value biases the runtime towards handling states.
+    fn updates_per_yield(&self) -> usize {
+        0
+    }
 }
 
 /// Operator that doesn't perform any operation upon state update
@@ -41,6 +63,7 @@ pub struct NoOperator<StateInput>(PhantomData<StateInput>);
 #[async_trait]
 impl<StateInput: ServiceState> StateOperator for NoOperator<StateInput> {
     type StateInput = StateInput;
+    const NOOP: bool = true;
 
     fn from_settings<Settings>(_settings: Settings) -> Self {
         NoOperator(PhantomData::default())
@@ -61,6 +84,27 @@ impl<Settings: Send + Sync + 'static> ServiceState for NoState<Settings> {
     }
 }
 
+/// Copy of a service state at a point in time
+#[derive(Clone, Debug)]
+pub struct StateSnapshot<S> {
+    /// Wall clock time the snapshot was taken at
+    pub taken_at: SystemTime,
+    pub state: S,
+}
+
+/// States that can be compared against another version of themselves
+pub trait StateDiff {
+    /// Description of what changed between two states
+    type Diff;
+    /// What changed from `previous` to `self`
+    fn diff(&self, previous: &Self) -> Self::Diff;
+}
+
+/// What changed in the state from the `earlier` snapshot to the `later` one
+pub fn diff<S: StateDiff>(earlier: &StateSnapshot<S>, later: &StateSnapshot<S>) -> S::Diff {
+    later.state.diff(&earlier.state)
+}
+
 /// Receiver part of the state handling mechanism.
 /// A state handle watches a stream of incoming states and triggers the attached operator handling
 /// method over it.
@@ -83,6 +127,21 @@ pub struct StateWatcher<S> {
     receiver: Receiver<S>,
 }
 
+/// Type erased [`StateWatcher`]
+pub type AnyStateWatcher = Box<dyn Any + Send + 'static>;
+
+/// State channel builder
+/// The channel always holds a value, starting with the provided initial state
+pub fn state_channel<S: ServiceState>(initial_state: S) -> (StateWatcher<S>, StateUpdater<S>) {
+    let (sender, receiver) = channel(initial_state);
+    (
+        StateWatcher { receiver },
+        StateUpdater {
+            sender: Arc::new(sender),
+        },
+    )
+}
+
 impl<S: ServiceState> StateUpdater<S> {
     /// Send a new state and notify the [`StateWatcher`]
     pub fn update(&mut self, new_state: S) {
@@ -119,35 +178,95 @@ where
     Operator: StateOperator<StateInput = S>,
 {
     pub fn new(initial_state: S, operator: Operator) -> (Self, StateUpdater<S>) {
-        let (sender, receiver) = channel(initial_state);
-        let watcher = StateWatcher { receiver };
-        let updater = StateUpdater {
-            sender: Arc::new(sender),
-        };
+        let (watcher, updater) = state_channel(initial_state);
+        (Self::with_watcher(watcher, operator), updater)
+    }
 
-        (Self { watcher, operator }, updater)
+    /// Build a handle over an already existing state channel
+    pub fn with_watcher(watcher: StateWatcher<S>, operator: Operator) -> Self {
+        Self { watcher, operator }
+    }
+
+    /// Get a new watcher of the state
+    /// It is seeded with the current state, so late subscribers see it right away
+    pub fn subscribe(&self) -> StateWatcher<S> {
+        self.watcher.clone()
     }
 
     /// Wait for new state updates and run the operator handling method
+    /// If the operator has a checkpoint interval, the current state is also checkpointed on it.
     pub async fn run(self) {
         let Self {
             watcher,
             mut operator,
         } = self;
-        let mut state_stream = WatchStream::new(watcher.receiver);
-        while let Some(state) = state_stream.next().await {
-            operator.run(state).await;
+        let mut state_stream = WatchStream::new(watcher.receiver.clone());
+        let mut fairness = Fairness::new(operator.updates_per_yield());
+        let interval = match operator.checkpoint_interval() {
+            Some(interval) => interval,
+            None => {
+                while let Some(state) = state_stream.next().await {
+                    operator.run(state).await;
+                    fairness.handled().await;
+                }
+                return;
+            }
+        };
+        let mut next_checkpoint = Instant::now() + interval;
+        loop {
+            match timeout_at(next_checkpoint, state_stream.next()).await {
+                Ok(Some(state)) => {
+                    operator.run(state).await;
+                    fairness.handled().await;
+                }
+                Ok(None) => break,
+                Err(_elapsed) => {
+                    operator.checkpoint(watcher.state_cloned()).await;
+                    next_checkpoint += interval;
+                }
+            }
+        }
+    }
+}
+
+/// Yields back to the runtime every few handled state updates
+struct Fairness {
+    updates_per_yield: usize,
+    handled: usize,
+}
+
+impl Fairness {
+    fn new(updates_per_yield: usize) -> Self {
+        Self {
+            updates_per_yield,
+            handled: 0,
+        }
+    }
+
+    async fn handled(&mut self) {
+        if self.updates_per_yield == 0 {
+            return;
+        }
+        self.handled += 1;
+        if self.handled >= self.updates_per_yield {
+            self.handled = 0;
+            yield_now().await;
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::services::state::{ServiceState, StateHandle, StateOperator, StateUpdater};
+    use crate::services::state::{
+        NoOperator, ServiceState, StateHandle, StateOperator, StateUpdater,
+    };
     use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
     use std::time::Duration;
     use tokio::io;
     use tokio::io::AsyncWriteExt;
+    use tokio::task::yield_now;
     use tokio::time::sleep;
 
     #[derive(Clone)]
@@ -182,6 +301,27 @@ mod test {
         }
     }
 
+    #[test]
+    fn only_no_operator_is_noop() {
+        // this shouldn't even compile if checks fails
+        const _: () = assert!(NoOperator::<UsizeCounter>::NOOP);
+        const _: () = assert!(!PanicOnGreaterThanTen::NOOP);
+    }
+
+    #[test]
+    fn late_subscriber_sees_current_state() {
+        let (handle, mut updater): (
+            StateHandle<UsizeCounter, PanicOnGreaterThanTen>,
+            StateUpdater<UsizeCounter>,
+        ) = StateHandle::new(
+            UsizeCounter::from_settings(&()),
+            PanicOnGreaterThanTen::from_settings(()),
+        );
+        updater.update(UsizeCounter(5));
+        let UsizeCounter(value) = handle.subscribe().state_cloned();
+        assert_eq!(value, 5);
+    }
+
     #[tokio::test]
     #[should_panic]
     async fn state_stream_collects() {
@@ -201,4 +341,119 @@ mod test {
         });
         handle.run().await;
     }
+
+    #[derive(Default)]
+    struct CheckpointCounter {
+        updates: Arc<AtomicUsize>,
+        checkpoints: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl StateOperator for CheckpointCounter {
+        type StateInput = UsizeCounter;
+
+        fn from_settings<Settings>(_settings: Settings) -> Self {
+            Self::default()
+        }
+
+        async fn run(&mut self, _state: Self::StateInput) {
+            self.updates.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn checkpoint_interval(&self) -> Option<Duration> {
+            Some(Duration::from_millis(100))
+        }
+
+        async fn checkpoint(&mut self, _state: Self::StateInput) {
+            self.checkpoints.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn checkpoints_follow_interval_not_updates() {
+        let operator = CheckpointCounter::from_settings(());
+        let updates = operator.updates.clone();
+        let checkpoints = operator.checkpoints.clone();
+        let (handle, mut updater) = StateHandle::new(UsizeCounter::from_settings(&()), operator);
+        let running = tokio::task::spawn(handle.run());
+        // updates every millisecond for 450 milliseconds
+        for i in 0..450 {
+            updater.update(UsizeCounter(i));
+            sleep(Duration::from_millis(1)).await;
+        }
+        drop(updater);
+        running.await.expect("State handle to finish");
+
+        assert!(updates.load(Ordering::SeqCst) > 10);
+        // one every 100 milliseconds
+        assert_eq!(checkpoints.load(Ordering::SeqCst), 4);
+    }
+
+    /// Operator busy for a while on every state, without ever awaiting
+    #[derive(Default)]
+    struct BlockingCounter {
+        updates: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl StateOperator for BlockingCounter {
+        type StateInput = UsizeCounter;
+
+        fn from_settings<Settings>(_settings: Settings) -> Self {
+            Self::default()
+        }
+
+        async fn run(&mut self, _state: Self::StateInput) {
+            std::thread::sleep(Duration::from_millis(1));
+            self.updates.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn updates_per_yield(&self) -> usize {
+            1
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn service_progresses_while_state_handling_is_busy() {
+        let operator = BlockingCounter::from_settings(());
+        let updates = operator.updates.clone();
+        let (handle, mut updater) = StateHandle::new(UsizeCounter::from_settings(&()), operator);
+        // produced from another thread, so there is always a new state waiting to be handled
+        // it gives up after a while, so a state handling that never yields fails instead of hanging
+        let producing = Arc::new(AtomicBool::new(true));
+        let producer = {
+            let producing = producing.clone();
+            std::thread::spawn(move || {
+                let started_at = std::time::Instant::now();
+                let mut i = 0;
+                while producing.load(Ordering::SeqCst)
+                    && started_at.elapsed() < Duration::from_secs(2)
+                {
+                    updater.update(UsizeCounter(i));
+                    i += 1;
+                }
+            })
+        };
+        let running = tokio::task::spawn(handle.run());
+        // stands for the service main loop, sharing the runtime with the state handling
+        let service = tokio::task::spawn({
+            let updates = updates.clone();
+            async move {
+                let mut iterations = 0;
+                while updates.load(Ordering::SeqCst) < 100 {
+                    iterations += 1;
+                    yield_now().await;
+                }
+                iterations
+            }
+        });
+        let iterations = service.await.expect("Service to finish");
+        producing.store(false, Ordering::SeqCst);
+        producer.join().expect("Producer to finish");
+        running.await.expect("State handle to finish");
+
+        // the state handling gave the service a turn after every state instead of holding the
+        // runtime for the whole churn
+        assert!(iterations >= 50, "{iterations} service iterations");
+    }
 }
@@ -0,0 +1,76 @@
+// std
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+// crates
+// internal
+
+/// Source of time for the services
+/// Services reading time through it, instead of `Instant::now`/`SystemTime::now`, can be
+/// tested deterministically by running them with a [`ManualClock`].
+pub trait Clock: Debug + Send + Sync + 'static {
+    /// Monotonic current time
+    fn now(&self) -> Instant;
+
+    /// Wall clock current time
+    fn system_time(&self) -> SystemTime;
+}
+
+/// [`Clock`] backed by the system time
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// [`Clock`] that only moves forward when it is advanced
+/// Clones share the same time, so a test can keep one and advance the time services see.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    instant: Instant,
+    system_time: SystemTime,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl ManualClock {
+    /// Clock frozen at the current system time
+    pub fn new() -> Self {
+        Self {
+            instant: Instant::now(),
+            system_time: SystemTime::now(),
+            elapsed: Default::default(),
+        }
+    }
+
+    /// Move the clock time forward
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+    }
+
+    fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.instant + self.elapsed()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        self.system_time + self.elapsed()
+    }
+}
@@ -1,2 +1,3 @@
+pub mod clock;
 pub mod const_checks;
 pub mod runtime;
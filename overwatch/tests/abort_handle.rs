@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+
+pub struct CounterService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for CounterService {
+    const SERVICE_ID: ServiceId = "CounterService";
+    type Settings = Arc<AtomicUsize>;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for CounterService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let counter = self.state.settings_reader.get_updated_settings();
+        loop {
+            counter.fetch_add(1, Ordering::SeqCst);
+            sleep(Duration::from_millis(10)).await;
+        }
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    counter_service: ServiceHandle<CounterService>,
+}
+
+#[test]
+fn abort_service_with_raw_handle() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let settings: TestAppServiceSettings = TestAppServiceSettings {
+        counter_service: counter.clone(),
+    };
+    let overwatch = OverwatchRunner::<TestApp>::run(settings, None);
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        sleep(Duration::from_millis(100)).await;
+        let abort_handle = handle
+            .abort_handle::<CounterService>()
+            .await
+            .expect("Counter service to be running");
+        abort_handle.abort();
+        sleep(Duration::from_millis(50)).await;
+        let aborted_at = counter.load(Ordering::SeqCst);
+        assert!(aborted_at > 0);
+        sleep(Duration::from_millis(200)).await;
+        assert_eq!(counter.load(Ordering::SeqCst), aborted_at);
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::bridge::BridgeRelay;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::{OutboundRelay, RelayMessage};
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::time::timeout;
+
+/// Service of the downstream app, answers every request
+pub struct ResponderService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Debug)]
+pub struct Request(oneshot::Sender<&'static str>);
+
+impl RelayMessage for Request {}
+
+impl ServiceData for ResponderService {
+    const SERVICE_ID: ServiceId = "ResponderService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = Request;
+}
+
+#[async_trait]
+impl ServiceCore for ResponderService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while let Some(Request(reply)) = self.state.inbound_relay.recv().await {
+            let _ = reply.send("answered by the downstream app");
+        }
+    }
+}
+
+/// Relay across the bridge to the downstream app, filled in once that app is running
+type Bridge = Arc<Mutex<Option<OutboundRelay<Request>>>>;
+
+/// Service of the upstream app, forwards requests through the bridge
+pub struct RequesterService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Debug)]
+pub struct Forward(oneshot::Sender<&'static str>);
+
+impl RelayMessage for Forward {}
+
+impl ServiceData for RequesterService {
+    const SERVICE_ID: ServiceId = "RequesterService";
+    type Settings = Bridge;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = Forward;
+}
+
+#[async_trait]
+impl ServiceCore for RequesterService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let bridge = self.state.settings_reader.get_updated_settings();
+        while let Some(Forward(reply)) = self.state.inbound_relay.recv().await {
+            let relay = bridge.lock().unwrap().clone().expect("Bridge to be set");
+            relay
+                .send(Request(reply))
+                .await
+                .expect("Request to cross the bridge");
+        }
+    }
+}
+
+#[derive(Services)]
+struct DownstreamApp {
+    responder: ServiceHandle<ResponderService>,
+}
+
+#[derive(Services)]
+struct UpstreamApp {
+    requester: ServiceHandle<RequesterService>,
+}
+
+#[test]
+fn relay_bridges_two_apps() {
+    let downstream =
+        OverwatchRunner::<DownstreamApp>::run(DownstreamAppServiceSettings { responder: () }, None);
+    let bridge = Bridge::default();
+    let upstream = OverwatchRunner::<UpstreamApp>::run(
+        UpstreamAppServiceSettings {
+            requester: bridge.clone(),
+        },
+        None,
+    );
+    let mut downstream_handle = downstream.handle().clone();
+    let mut upstream_handle = upstream.handle().clone();
+
+    upstream.runtime().block_on(async move {
+        let bridge_relay = BridgeRelay::connect::<ResponderService>(&downstream_handle, 8)
+            .await
+            .expect("Responder service to be running");
+        let source = bridge_relay.relay();
+        *bridge.lock().unwrap() = Some(bridge_relay.relay());
+
+        let requester = upstream_handle
+            .relay::<RequesterService>()
+            .connect()
+            .await
+            .expect("Requester service to be running");
+        let (reply, receiver) = oneshot::channel();
+        requester
+            .send(Forward(reply))
+            .await
+            .expect("Request to be sent");
+        assert_eq!(receiver.await, Ok("answered by the downstream app"));
+
+        // stopping the downstream app closes the bridge for the upstream one
+        downstream_handle.shutdown().await;
+        timeout(Duration::from_secs(1), bridge_relay.closed())
+            .await
+            .expect("Bridge to close once the downstream app stops");
+        assert!(source.is_closed());
+        upstream_handle.shutdown().await;
+    });
+
+    upstream.wait_finished();
+    downstream.wait_finished();
+}
@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::{NoMessage, RelayBufferLimits, RelayMessage};
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+/// Non critical service that fails to start, its buffer is over the hard limit
+pub struct ReportingService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for ReportingService {
+    const SERVICE_ID: ServiceId = "ReportingService";
+    const SERVICE_RELAY_BUFFER_SIZE: usize = 1_000;
+    const CRITICAL: bool = false;
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for ReportingService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+pub struct CoreService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Debug)]
+pub struct Ping(oneshot::Sender<()>);
+
+impl RelayMessage for Ping {}
+
+impl ServiceData for CoreService {
+    const SERVICE_ID: ServiceId = "CoreService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = Ping;
+}
+
+#[async_trait]
+impl ServiceCore for CoreService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while let Some(Ping(reply)) = self.state.inbound_relay.recv().await {
+            let _ = reply.send(());
+        }
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    reporting: ServiceHandle<ReportingService>,
+    core: ServiceHandle<CoreService>,
+}
+
+#[test]
+fn app_runs_without_failed_non_critical_service() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings {
+        reporting: (),
+        core: (),
+    };
+    let ready = Arc::new(AtomicBool::new(false));
+    let on_ready = ready.clone();
+    let overwatch = OverwatchRunner::<TestApp>::builder(settings)
+        .relay_buffer_limits(RelayBufferLimits {
+            soft: 10,
+            hard: 100,
+        })
+        .best_effort()
+        .on_ready(move |_| on_ready.store(true, Ordering::SeqCst))
+        .run()
+        .expect("Critical services to start");
+
+    let report = overwatch.startup_report();
+    assert_eq!(report.started, ["CoreService"]);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, "ReportingService");
+    // the app is degraded, not ready
+    assert!(!ready.load(Ordering::SeqCst));
+
+    let mut handle = overwatch.handle().clone();
+    overwatch.runtime().block_on(async move {
+        let relay = handle
+            .relay::<CoreService>()
+            .connect()
+            .await
+            .expect("Core service to be running");
+        let (reply, receiver) = oneshot::channel();
+        relay.send(Ping(reply)).await.expect("Ping to be sent");
+        receiver.await.expect("Core service to answer");
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
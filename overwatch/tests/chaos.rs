@@ -0,0 +1,106 @@
+#![cfg(feature = "chaos")]
+
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::chaos::{FaultInjection, FaultInjector};
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::{relay, RelayMessage};
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::time::timeout;
+
+pub struct PongService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Debug)]
+pub struct Ping(oneshot::Sender<()>);
+
+impl RelayMessage for Ping {}
+
+impl ServiceData for PongService {
+    const SERVICE_ID: ServiceId = "PongService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = Ping;
+}
+
+#[async_trait]
+impl ServiceCore for PongService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while let Some(Ping(reply)) = self.state.inbound_relay.recv().await {
+            let _ = reply.send(());
+        }
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    pong: ServiceHandle<PongService>,
+}
+
+#[test]
+fn dropped_messages_never_reach_the_service() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings { pong: () };
+    let overwatch = OverwatchRunner::<TestApp>::builder(settings)
+        .fault_injection(
+            "PongService",
+            FaultInjection {
+                drop_rate: 1.0,
+                ..Default::default()
+            },
+        )
+        .run()
+        .expect("Services to start");
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let relay = handle
+            .relay::<PongService>()
+            .connect()
+            .await
+            .expect("Pong service to be running");
+        for _ in 0..10 {
+            let (reply, receiver) = oneshot::channel();
+            relay.send(Ping(reply)).await.expect("Ping to be sent");
+            // the ping is dropped along with its reply channel
+            assert!(receiver.await.is_err());
+        }
+
+        handle
+            .fault_injector("PongService")
+            .expect("Faults to be configured")
+            .set(FaultInjection::default());
+        let (reply, receiver) = oneshot::channel();
+        relay.send(Ping(reply)).await.expect("Ping to be sent");
+        receiver.await.expect("Pong service to answer");
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
+
+#[tokio::test]
+async fn delayed_messages_survive_cancelled_receives() {
+    let (inbound_relay, outbound_relay) = relay::<u32>(8);
+    let mut inbound_relay = inbound_relay.with_faults(FaultInjector::new(FaultInjection {
+        delay: Duration::from_millis(200),
+        ..Default::default()
+    }));
+    outbound_relay.send(1).await.expect("Message to be sent");
+    assert!(timeout(Duration::from_millis(50), inbound_relay.recv())
+        .await
+        .is_err());
+    assert_eq!(
+        timeout(Duration::from_secs(1), inbound_relay.recv()).await,
+        Ok(Some(1))
+    );
+}
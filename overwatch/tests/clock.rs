@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::RelayMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch::utils::clock::ManualClock;
+use overwatch_derive::Services;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+pub struct UptimeService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Debug)]
+pub struct Uptime(oneshot::Sender<Duration>);
+
+impl RelayMessage for Uptime {}
+
+impl ServiceData for UptimeService {
+    const SERVICE_ID: ServiceId = "UptimeService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = Uptime;
+}
+
+#[async_trait]
+impl ServiceCore for UptimeService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let started_at = self.state.now();
+        while let Some(Uptime(reply)) = self.state.inbound_relay.recv().await {
+            let _ = reply.send(self.state.now() - started_at);
+        }
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    uptime: ServiceHandle<UptimeService>,
+}
+
+#[test]
+fn service_time_follows_manual_clock() {
+    let clock = ManualClock::new();
+    let settings: TestAppServiceSettings = TestAppServiceSettings { uptime: () };
+    let overwatch = OverwatchRunner::<TestApp>::builder(settings)
+        .clock(clock.clone())
+        .run()
+        .expect("Services to start");
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let relay = handle
+            .relay::<UptimeService>()
+            .connect()
+            .await
+            .expect("Uptime service to be running");
+        let uptime = || async {
+            let (reply, receiver) = oneshot::channel();
+            relay.send(Uptime(reply)).await.expect("Message to be sent");
+            receiver.await.expect("Uptime service to answer")
+        };
+
+        assert_eq!(uptime().await, Duration::ZERO);
+        clock.advance(Duration::from_secs(3600));
+        assert_eq!(uptime().await, Duration::from_secs(3600));
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use overwatch::overwatch::connections::{
+    ConnectionManager, ConnectionPool, PoolError, PoolSettings,
+};
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Connection that can be marked as broken by its user
+pub struct FakeConnection {
+    id: usize,
+    broken: bool,
+}
+
+/// Opens numbered connections and rejects the broken ones on checkout
+#[derive(Default)]
+pub struct FakeManager {
+    opened: AtomicUsize,
+}
+
+#[async_trait]
+impl ConnectionManager for FakeManager {
+    type Connection = FakeConnection;
+    type Error = ();
+
+    async fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        Ok(FakeConnection {
+            id: self.opened.fetch_add(1, Ordering::SeqCst),
+            broken: false,
+        })
+    }
+
+    async fn is_valid(&self, connection: &mut Self::Connection) -> bool {
+        !connection.broken
+    }
+}
+
+pub struct IdleService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for IdleService {
+    const SERVICE_ID: ServiceId = "IdleService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for IdleService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    idle: ServiceHandle<IdleService>,
+}
+
+#[tokio::test]
+async fn returned_connections_are_validated_and_reused() {
+    let pool = ConnectionPool::new(FakeManager::default(), PoolSettings::default());
+
+    let first = pool.checkout().await.expect("A connection");
+    assert_eq!(first.id, 0);
+    drop(first);
+    assert_eq!(pool.idle_connections(), 1);
+
+    // the idle connection is reused, then marked broken while in use
+    let mut reused = pool.checkout().await.expect("A connection");
+    assert_eq!(reused.id, 0);
+    reused.broken = true;
+    drop(reused);
+
+    // the broken connection fails validation and a new one is opened instead
+    let fresh = pool.checkout().await.expect("A connection");
+    assert_eq!(fresh.id, 1);
+    drop(fresh);
+
+    pool.close();
+    assert_eq!(pool.idle_connections(), 0);
+    assert!(matches!(pool.checkout().await, Err(PoolError::Closed)));
+}
+
+#[test]
+fn managed_pool_is_closed_on_shutdown() {
+    let overwatch = OverwatchRunner::<TestApp>::builder(TestAppServiceSettings { idle: () })
+        .managed_resource(ConnectionPool::new(
+            FakeManager::default(),
+            PoolSettings::default(),
+        ))
+        .run()
+        .expect("Services to start");
+    let mut handle = overwatch.handle().clone();
+    let pool = handle
+        .resource::<ConnectionPool<FakeManager>>()
+        .expect("Connection pool to be registered");
+
+    overwatch.runtime().block_on(async {
+        let connection = pool.checkout().await.expect("A connection");
+        assert_eq!(connection.id, 0);
+        drop(connection);
+        assert_eq!(pool.idle_connections(), 1);
+        handle.shutdown().await;
+    });
+    overwatch.wait_finished();
+
+    assert_eq!(pool.idle_connections(), 0);
+    assert!(matches!(
+        futures::executor::block_on(pool.checkout()),
+        Err(PoolError::Closed)
+    ));
+}
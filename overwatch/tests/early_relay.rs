@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use overwatch::overwatch::{Error, OverwatchRunner};
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::RelayMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::time::timeout;
+
+pub struct LateService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Debug)]
+pub struct Greet(oneshot::Sender<&'static str>);
+
+impl RelayMessage for Greet {}
+
+impl ServiceData for LateService {
+    const SERVICE_ID: ServiceId = "LateService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = Greet;
+}
+
+#[async_trait]
+impl ServiceCore for LateService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while let Some(Greet(reply)) = self.state.inbound_relay.recv().await {
+            let _ = reply.send("hello from a late service");
+        }
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    late: ServiceHandle<LateService>,
+}
+
+#[test]
+fn messages_sent_before_start_are_received_once_started() {
+    let overwatch = OverwatchRunner::<TestApp>::builder(TestAppServiceSettings { late: () })
+        .defer_start("LateService")
+        .run()
+        .expect("Services to start");
+    assert!(overwatch.startup_report().started.is_empty());
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let relay = handle
+            .relay::<LateService>()
+            .connect()
+            .await
+            .expect("Relay to be available before the service starts");
+        let (reply, mut receiver) = oneshot::channel();
+        relay
+            .send(Greet(reply))
+            .await
+            .expect("Greet to be buffered");
+        assert!(timeout(Duration::from_millis(100), &mut receiver)
+            .await
+            .is_err());
+
+        handle
+            .start("LateService")
+            .await
+            .expect("Late service to start");
+        assert_eq!(receiver.await, Ok("hello from a late service"));
+        assert!(matches!(
+            handle.start("LateService").await,
+            Err(Error::NotDeferred {
+                service_id: "LateService"
+            })
+        ));
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
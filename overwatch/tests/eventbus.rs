@@ -0,0 +1,84 @@
+#![cfg(feature = "eventbus")]
+
+use futures::StreamExt;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::eventbus::{BusEvent, EventBusRelay, EventBusService, EventBusSettings};
+use overwatch::services::handle::ServiceHandle;
+use overwatch::services::ServiceId;
+use overwatch_derive::Services;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct News(String);
+
+impl BusEvent for News {
+    const BUS_ID: ServiceId = "NewsBus";
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Alert(u32);
+
+impl BusEvent for Alert {
+    const BUS_ID: ServiceId = "AlertBus";
+}
+
+#[derive(Services)]
+struct TestApp {
+    news_bus: ServiceHandle<EventBusService<News>>,
+    alert_bus: ServiceHandle<EventBusService<Alert>>,
+}
+
+#[test]
+fn published_event_reaches_every_subscriber() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings {
+        news_bus: EventBusSettings::default(),
+        alert_bus: EventBusSettings::default(),
+    };
+    let overwatch = OverwatchRunner::<TestApp>::run(settings, None);
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let news_bus = EventBusRelay::new(
+            handle
+                .relay::<EventBusService<News>>()
+                .connect()
+                .await
+                .expect("A connection to the news bus is established"),
+        );
+        let alert_bus = EventBusRelay::new(
+            handle
+                .relay::<EventBusService<Alert>>()
+                .connect()
+                .await
+                .expect("A connection to the alert bus is established"),
+        );
+
+        let mut first = news_bus
+            .subscribe("news")
+            .await
+            .expect("First subscription to succeed");
+        let mut second = news_bus
+            .subscribe("news")
+            .await
+            .expect("Second subscription to succeed");
+        let mut alerts = alert_bus
+            .subscribe("alerts")
+            .await
+            .expect("Alert subscription to succeed");
+        news_bus
+            .publish("news", News("Hey oh let's go!".to_string()))
+            .await
+            .expect("Event to be published");
+        alert_bus
+            .publish("alerts", Alert(7))
+            .await
+            .expect("Alert to be published");
+
+        let expected = News("Hey oh let's go!".to_string());
+        assert_eq!(first.next().await, Some(expected.clone()));
+        assert_eq!(second.next().await, Some(expected));
+        assert_eq!(alerts.next().await, Some(Alert(7)));
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
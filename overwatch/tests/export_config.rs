@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+
+pub struct Network {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for Network {
+    const SERVICE_ID: ServiceId = "Network";
+    type Settings = String;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for Network {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+pub struct Storage {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for Storage {
+    const SERVICE_ID: ServiceId = "Storage";
+    type Settings = usize;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for Storage {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    network: ServiceHandle<Network>,
+    storage: ServiceHandle<Storage>,
+}
+
+#[test]
+fn exported_config_reflects_live_updates() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings {
+        network: "127.0.0.1:3000".to_string(),
+        storage: 64,
+    };
+    let updated = TestAppServiceSettings {
+        network: settings.network.clone(),
+        storage: 128,
+    };
+    let overwatch = OverwatchRunner::<TestApp>::run(settings, None);
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let exported = handle
+            .export_config::<TestApp>()
+            .await
+            .expect("Settings to be exported");
+        assert_eq!(exported.network, "127.0.0.1:3000");
+        assert_eq!(exported.storage, 64);
+
+        handle
+            .update_settings::<TestApp>(updated)
+            .await
+            .expect("Settings to be applied");
+        let exported = handle
+            .export_config::<TestApp>()
+            .await
+            .expect("Settings to be exported");
+        assert_eq!(exported.network, "127.0.0.1:3000");
+        assert_eq!(exported.storage, 128);
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
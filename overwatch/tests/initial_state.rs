@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use overwatch::overwatch::handle::OverwatchHandle;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{ServiceState, StateOperator};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::time::sleep;
+
+static OBSERVED_VALUE: AtomicUsize = AtomicUsize::new(0);
+
+pub struct StatefulService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Clone)]
+pub struct CounterState {
+    value: usize,
+}
+
+impl ServiceState for CounterState {
+    type Settings = ();
+
+    fn from_settings(_settings: &Self::Settings) -> Self {
+        Self { value: 0 }
+    }
+}
+
+#[derive(Clone)]
+pub struct RecordStateOperator;
+
+#[async_trait]
+impl StateOperator for RecordStateOperator {
+    type StateInput = CounterState;
+
+    fn from_settings<Settings>(_settings: Settings) -> Self {
+        RecordStateOperator
+    }
+
+    async fn run(&mut self, state: Self::StateInput) {
+        OBSERVED_VALUE.store(state.value, Ordering::SeqCst);
+    }
+}
+
+impl ServiceData for StatefulService {
+    const SERVICE_ID: ServiceId = "StatefulService";
+    type Settings = ();
+    type State = CounterState;
+    type StateOperator = RecordStateOperator;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for StatefulService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+#[test]
+fn service_starts_from_injected_state() {
+    let runtime = tokio::runtime::Runtime::new().expect("Runtime to be built");
+    let (sender, _receiver) = tokio::sync::mpsc::channel(1);
+    let overwatch_handle = OverwatchHandle::new(runtime.handle().clone(), sender);
+    let mut service_handle = ServiceHandle::<StatefulService>::with_state(
+        (),
+        overwatch_handle,
+        CounterState { value: 42 },
+    );
+
+    service_handle.service_runner().run();
+    runtime.block_on(async { sleep(Duration::from_millis(100)).await });
+
+    assert_eq!(OBSERVED_VALUE.load(Ordering::SeqCst), 42);
+}
@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::RelayMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+pub struct EchoService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Debug)]
+pub struct EchoMessage(tokio::sync::oneshot::Sender<()>);
+
+impl RelayMessage for EchoMessage {}
+
+impl ServiceData for EchoService {
+    const SERVICE_ID: ServiceId = "EchoService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = EchoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for EchoService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while let Some(EchoMessage(reply)) = self.state.inbound_relay.recv().await {
+            let _ = reply.send(());
+        }
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    echo_service: ServiceHandle<EchoService>,
+}
+
+#[test]
+fn on_ready_fires_once_services_are_running() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings { echo_service: () };
+    let ready_calls = Arc::new(AtomicUsize::new(0));
+    let hook_calls = ready_calls.clone();
+    let overwatch = OverwatchRunner::<TestApp>::builder(settings)
+        .on_ready(move |mut handle| {
+            hook_calls.fetch_add(1, Ordering::SeqCst);
+            handle.runtime().clone().spawn(async move {
+                let relay = handle
+                    .relay::<EchoService>()
+                    .connect()
+                    .await
+                    .expect("Echo service to be running");
+                let (reply, receiver) = tokio::sync::oneshot::channel();
+                relay.send(EchoMessage(reply)).await.expect("Echo sent");
+                receiver.await.expect("Echo service to answer");
+                handle.shutdown().await;
+            });
+        })
+        .run()
+        .expect("Services to start");
+
+    overwatch.wait_finished();
+    assert_eq!(ready_calls.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,196 @@
+#![cfg(feature = "testing")]
+
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::record::replay;
+use overwatch::services::relay::{relay, RelayMessage};
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Command(u32, String);
+
+impl RelayMessage for Command {}
+
+/// Commands the service received, in order
+#[derive(Default)]
+pub struct Received(Mutex<Vec<Command>>);
+
+pub struct CommandService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for CommandService {
+    const SERVICE_ID: ServiceId = "CommandService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = Command;
+}
+
+#[async_trait]
+impl ServiceCore for CommandService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let received = self
+            .state
+            .resource::<Received>()
+            .expect("Received commands to be registered");
+        while let Some(command) = self.state.inbound_relay.recv().await {
+            received.0.lock().unwrap().push(command);
+        }
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    commands: ServiceHandle<CommandService>,
+}
+
+fn sequence() -> Vec<Command> {
+    vec![
+        Command(1, "connect".to_string()),
+        Command(2, "subscribe".to_string()),
+        Command(3, "publish".to_string()),
+        Command(2, "subscribe".to_string()),
+    ]
+}
+
+fn recording_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("overwatch-{name}-{}.jsonl", std::process::id()))
+}
+
+/// Recordings are written in the background, wait until every message made it to the file
+async fn wait_for_recording(path: &Path, messages: usize) {
+    timeout(Duration::from_secs(1), async {
+        while std::fs::read_to_string(path)
+            .map(|recording| recording.lines().count())
+            .unwrap_or_default()
+            < messages
+        {
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("Every message to be recorded");
+}
+
+async fn wait_for_received(received: &Received, commands: usize) {
+    timeout(Duration::from_secs(1), async {
+        while received.0.lock().unwrap().len() < commands {
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("Every command to be received");
+}
+
+#[tokio::test]
+async fn replayed_sequence_matches_recording() {
+    let path = recording_path("recording");
+    let sequence = sequence();
+
+    let (inbound_relay, outbound_relay) = relay::<Command>(8);
+    let mut inbound_relay = inbound_relay
+        .record(&path)
+        .expect("Recording file to be created");
+    for message in sequence.clone() {
+        outbound_relay
+            .send(message)
+            .await
+            .expect("Message to be sent");
+    }
+    let mut received = Vec::new();
+    for _ in 0..sequence.len() {
+        received.push(inbound_relay.recv().await.expect("Message to be received"));
+    }
+    drop(inbound_relay);
+    wait_for_recording(&path, sequence.len()).await;
+
+    let (mut inbound_relay, outbound_relay) = relay::<Command>(8);
+    let replayed = replay(&path, &outbound_relay)
+        .await
+        .expect("Recording to be replayed");
+    drop(outbound_relay);
+    let mut replayed_messages = Vec::new();
+    while let Some(message) = inbound_relay.recv().await {
+        replayed_messages.push(message);
+    }
+    std::fs::remove_file(&path).expect("Recording file to be removed");
+
+    assert_eq!(replayed, sequence.len());
+    assert_eq!(received, sequence);
+    assert_eq!(replayed_messages, sequence);
+}
+
+#[test]
+fn service_replay_matches_its_recording() {
+    let path = recording_path("service-recording");
+    let sequence = sequence();
+
+    let overwatch = OverwatchRunner::<TestApp>::builder(TestAppServiceSettings { commands: () })
+        .resource(Received::default())
+        .record_relay::<CommandService>(&path)
+        .expect("Recording file to be created")
+        .run()
+        .expect("Services to start");
+    let mut handle = overwatch.handle().clone();
+    let recorded = handle
+        .resource::<Received>()
+        .expect("Received commands to be registered");
+    let (recording, commands, received) = (path.clone(), sequence.clone(), recorded.clone());
+    overwatch.runtime().block_on(async move {
+        let expected = commands.len();
+        let relay = handle
+            .relay::<CommandService>()
+            .connect()
+            .await
+            .expect("Command service to be running");
+        for command in commands {
+            relay.send(command).await.expect("Command to be sent");
+        }
+        wait_for_received(&received, expected).await;
+        wait_for_recording(&recording, expected).await;
+        handle.shutdown().await;
+    });
+    overwatch.wait_finished();
+
+    // a fresh app, without recording, fed with the recording
+    let overwatch = OverwatchRunner::<TestApp>::builder(TestAppServiceSettings { commands: () })
+        .resource(Received::default())
+        .run()
+        .expect("Services to start");
+    let mut handle = overwatch.handle().clone();
+    let replayed = handle
+        .resource::<Received>()
+        .expect("Received commands to be registered");
+    let (recording, expected, received) = (path.clone(), sequence.len(), replayed.clone());
+    overwatch.runtime().block_on(async move {
+        let relay = handle
+            .relay::<CommandService>()
+            .connect()
+            .await
+            .expect("Command service to be running");
+        let sent = replay(&recording, &relay)
+            .await
+            .expect("Recording to be replayed");
+        assert_eq!(sent, expected);
+        wait_for_received(&received, expected).await;
+        handle.shutdown().await;
+    });
+    overwatch.wait_finished();
+    std::fs::remove_file(&path).expect("Recording file to be removed");
+
+    assert_eq!(*recorded.0.lock().unwrap(), sequence);
+    assert_eq!(*replayed.0.lock().unwrap(), sequence);
+}
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use overwatch::overwatch::{Error, OverwatchRunner};
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::{NoMessage, RelayBufferLimits, RelayError};
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceError, ServiceId};
+use overwatch_derive::Services;
+
+pub struct HugeBufferService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for HugeBufferService {
+    const SERVICE_ID: ServiceId = "HugeBufferService";
+    // one zero too many
+    const SERVICE_RELAY_BUFFER_SIZE: usize = 1_000;
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for HugeBufferService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    huge_buffer: ServiceHandle<HugeBufferService>,
+}
+
+#[test]
+fn buffer_over_hard_limit_fails_startup() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings { huge_buffer: () };
+    let result = OverwatchRunner::<TestApp>::builder(settings)
+        .relay_buffer_limits(RelayBufferLimits {
+            soft: 10,
+            hard: 100,
+        })
+        .run();
+
+    match result {
+        Err(Error::Relay(ServiceError::RelayError(RelayError::BufferTooLarge {
+            service_id,
+            buffer_size,
+            limit,
+        }))) => {
+            assert_eq!(service_id, "HugeBufferService");
+            assert_eq!(buffer_size, 1_000);
+            assert_eq!(limit, 100);
+        }
+        Err(e) => panic!("Unexpected startup error {e:?}"),
+        Ok(_) => panic!("Startup to fail"),
+    }
+}
@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::RelayMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::time::Duration;
+use tokio::time::timeout;
+
+/// Service that stops as soon as it is told to
+pub struct OneShotService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Debug)]
+pub struct Stop;
+
+impl RelayMessage for Stop {}
+
+impl ServiceData for OneShotService {
+    const SERVICE_ID: ServiceId = "OneShotService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = Stop;
+}
+
+#[async_trait]
+impl ServiceCore for OneShotService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        self.state.inbound_relay.recv().await;
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    one_shot: ServiceHandle<OneShotService>,
+}
+
+#[test]
+fn relay_closes_when_service_stops() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings { one_shot: () };
+    let overwatch = OverwatchRunner::<TestApp>::run(settings, None);
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let relay = handle
+            .relay::<OneShotService>()
+            .connect()
+            .await
+            .expect("One shot service to be running");
+        assert!(!relay.is_closed());
+        relay.send(Stop).await.expect("Stop to be sent");
+        timeout(Duration::from_secs(1), relay.closed())
+            .await
+            .expect("Relay to close once the service stops");
+        assert!(relay.is_closed());
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
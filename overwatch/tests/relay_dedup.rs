@@ -0,0 +1,51 @@
+use overwatch::services::relay::{relay, DedupWindow};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn dedup_drops_repeated_messages_within_the_window() {
+    let (inbound_relay, outbound_relay) = relay::<String>(8);
+    let mut inbound_relay = inbound_relay.dedup(DedupWindow {
+        max_entries: 2,
+        ttl: Duration::from_millis(100),
+    });
+    let send = |message: &str| outbound_relay.send(message.to_string());
+
+    send("retried").await.expect("Message to be sent");
+    send("retried").await.expect("Message to be sent");
+    send("fresh").await.expect("Message to be sent");
+    assert_eq!(inbound_relay.recv().await.as_deref(), Some("retried"));
+    assert_eq!(inbound_relay.recv().await.as_deref(), Some("fresh"));
+    assert_eq!(inbound_relay.deduped(), 1);
+
+    // a third message pushes the oldest one out of the full window, so it is delivered again
+    send("other").await.expect("Message to be sent");
+    send("retried").await.expect("Message to be sent");
+    assert_eq!(inbound_relay.recv().await.as_deref(), Some("other"));
+    assert_eq!(inbound_relay.recv().await.as_deref(), Some("retried"));
+
+    // messages older than the ttl are forgotten
+    sleep(Duration::from_millis(200)).await;
+    send("other").await.expect("Message to be sent");
+    assert_eq!(inbound_relay.recv().await.as_deref(), Some("other"));
+    assert_eq!(inbound_relay.deduped(), 1);
+}
+
+#[tokio::test]
+async fn empty_window_disables_dedup() {
+    let (inbound_relay, outbound_relay) = relay::<String>(8);
+    let mut inbound_relay = inbound_relay.dedup(DedupWindow {
+        max_entries: 0,
+        ttl: Duration::from_secs(10),
+    });
+    for _ in 0..3 {
+        outbound_relay
+            .send("retried".to_string())
+            .await
+            .expect("Message to be sent");
+    }
+    for _ in 0..3 {
+        assert_eq!(inbound_relay.recv().await.as_deref(), Some("retried"));
+    }
+    assert_eq!(inbound_relay.deduped(), 0);
+}
@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::RelayMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::collections::HashSet;
+use tokio::sync::oneshot;
+
+const PRODUCERS: usize = 16;
+const MESSAGES_PER_PRODUCER: usize = 50;
+
+pub struct SinkService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Debug)]
+pub enum SinkMessage {
+    Item { producer: usize, sequence: usize },
+    Collected(oneshot::Sender<HashSet<(usize, usize)>>),
+}
+
+impl RelayMessage for SinkMessage {}
+
+impl ServiceData for SinkService {
+    const SERVICE_ID: ServiceId = "SinkService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = SinkMessage;
+}
+
+#[async_trait]
+impl ServiceCore for SinkService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let mut collected = HashSet::new();
+        while let Some(message) = self.state.inbound_relay.recv().await {
+            match message {
+                SinkMessage::Item { producer, sequence } => {
+                    collected.insert((producer, sequence));
+                }
+                SinkMessage::Collected(reply) => {
+                    let _ = reply.send(collected.clone());
+                }
+            }
+        }
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    sink: ServiceHandle<SinkService>,
+}
+
+#[test]
+fn every_message_from_many_senders_arrives() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings { sink: () };
+    let overwatch = OverwatchRunner::<TestApp>::run(settings, None);
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let relays = handle
+            .relay_many::<SinkService>(PRODUCERS)
+            .await
+            .expect("Sink service to be running");
+        assert_eq!(relays.len(), PRODUCERS);
+        let producers: Vec<_> = relays
+            .into_iter()
+            .enumerate()
+            .map(|(producer, relay)| {
+                tokio::spawn(async move {
+                    for sequence in 0..MESSAGES_PER_PRODUCER {
+                        relay
+                            .send(SinkMessage::Item { producer, sequence })
+                            .await
+                            .expect("Message to be sent");
+                    }
+                })
+            })
+            .collect();
+        for producer in producers {
+            producer.await.expect("Producer to finish");
+        }
+
+        let relay = handle
+            .relay::<SinkService>()
+            .connect()
+            .await
+            .expect("Sink service to be running");
+        let (reply, receiver) = oneshot::channel();
+        relay
+            .send(SinkMessage::Collected(reply))
+            .await
+            .expect("Message to be sent");
+        let collected = receiver.await.expect("Sink service to answer");
+        assert_eq!(collected.len(), PRODUCERS * MESSAGES_PER_PRODUCER);
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use futures::{stream, StreamExt};
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::RelayMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use tokio::sync::oneshot;
+
+pub struct CollectorService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Debug)]
+pub enum CollectorMsg {
+    Value(u32),
+    Collected(oneshot::Sender<Vec<u32>>),
+}
+
+impl RelayMessage for CollectorMsg {}
+
+impl ServiceData for CollectorService {
+    const SERVICE_ID: ServiceId = "CollectorService";
+    const SERVICE_RELAY_BUFFER_SIZE: usize = 2;
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = CollectorMsg;
+}
+
+#[async_trait]
+impl ServiceCore for CollectorService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let mut collected = Vec::new();
+        while let Some(message) = self.state.inbound_relay.recv().await {
+            match message {
+                CollectorMsg::Value(value) => collected.push(value),
+                CollectorMsg::Collected(reply) => {
+                    let _ = reply.send(collected.clone());
+                }
+            }
+        }
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    collector: ServiceHandle<CollectorService>,
+}
+
+#[test]
+fn stream_is_forwarded_into_service() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings { collector: () };
+    let overwatch = OverwatchRunner::<TestApp>::run(settings, None);
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let relay = handle
+            .relay::<CollectorService>()
+            .connect()
+            .await
+            .expect("Collector service to be running");
+        // more values than the relay buffers, the sink waits for the service to catch up
+        stream::iter(0..10)
+            .map(|value| Ok(CollectorMsg::Value(value)))
+            .forward(relay.clone().into_sink())
+            .await
+            .expect("Stream to be forwarded");
+
+        let (reply, receiver) = oneshot::channel();
+        relay
+            .send(CollectorMsg::Collected(reply))
+            .await
+            .expect("Message to be sent");
+        assert_eq!(receiver.await, Ok((0..10).collect::<Vec<_>>()));
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
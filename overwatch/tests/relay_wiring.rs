@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::{NoMessage, RelayMessage};
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use tokio::sync::oneshot;
+
+pub struct PingService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for PingService {
+    const SERVICE_ID: ServiceId = "PingService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for PingService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+pub struct PongService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Debug)]
+pub struct Ping(oneshot::Sender<&'static str>);
+
+impl RelayMessage for Ping {}
+
+impl ServiceData for PongService {
+    const SERVICE_ID: ServiceId = "PongService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = Ping;
+}
+
+#[async_trait]
+impl ServiceCore for PongService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while let Some(Ping(reply)) = self.state.inbound_relay.recv().await {
+            let _ = reply.send("pong");
+        }
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    #[overwatch(relays_to(PongService))]
+    ping: ServiceHandle<PingService>,
+    pong: ServiceHandle<PongService>,
+}
+
+#[test]
+fn declared_relay_accessor_reaches_target() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings { ping: (), pong: () };
+    let overwatch = OverwatchRunner::<TestApp>::run(settings, None);
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let relay = TestApp::relay_ping_to_pong(&handle)
+            .connect()
+            .await
+            .expect("Pong service to be running");
+        let (reply, receiver) = oneshot::channel();
+        relay.send(Ping(reply)).await.expect("Ping to be sent");
+        assert_eq!(receiver.await, Ok("pong"));
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+/// Stands for a pool shared by every service, counts the services that checked it out
+#[derive(Default)]
+pub struct SharedPool {
+    checkouts: AtomicUsize,
+}
+
+pub struct FirstService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for FirstService {
+    const SERVICE_ID: ServiceId = "FirstService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for FirstService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let pool = self
+            .state
+            .resource::<SharedPool>()
+            .expect("Shared pool to be registered");
+        pool.checkouts.fetch_add(1, Ordering::SeqCst);
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+pub struct SecondService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for SecondService {
+    const SERVICE_ID: ServiceId = "SecondService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for SecondService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let pool = self
+            .state
+            .resource::<SharedPool>()
+            .expect("Shared pool to be registered");
+        pool.checkouts.fetch_add(1, Ordering::SeqCst);
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    first: ServiceHandle<FirstService>,
+    second: ServiceHandle<SecondService>,
+}
+
+#[test]
+fn services_share_the_same_resource() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings {
+        first: (),
+        second: (),
+    };
+    let overwatch = OverwatchRunner::<TestApp>::builder(settings)
+        .resource(SharedPool::default())
+        .run()
+        .expect("Services to start");
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let pool = handle
+            .resource::<SharedPool>()
+            .expect("Shared pool to be registered");
+        timeout(Duration::from_secs(1), async {
+            while pool.checkouts.load(Ordering::SeqCst) < 2 {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Both services to check out the shared pool");
+        assert!(handle.resource::<String>().is_none());
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
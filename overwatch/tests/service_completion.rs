@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use overwatch::overwatch::handle::OverwatchHandle;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+/// Service finishing on its own, or panicking if its settings say so
+pub struct ShortLivedService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for ShortLivedService {
+    const SERVICE_ID: ServiceId = "ShortLivedService";
+    type Settings = bool;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for ShortLivedService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let panics = self.state.settings_reader.get_updated_settings();
+        sleep(Duration::from_millis(50)).await;
+        if panics {
+            panic!("Short lived service panicked");
+        }
+    }
+}
+
+fn service_handle(
+    runtime: &tokio::runtime::Runtime,
+    panics: bool,
+) -> ServiceHandle<ShortLivedService> {
+    let (sender, _receiver) = tokio::sync::mpsc::channel(1);
+    let overwatch_handle = OverwatchHandle::new(runtime.handle().clone(), sender);
+    ServiceHandle::<ShortLivedService>::new(panics, overwatch_handle)
+}
+
+#[test]
+fn await_short_lived_service_completion() {
+    let runtime = tokio::runtime::Runtime::new().expect("Runtime to be built");
+    let mut finishing = service_handle(&runtime, false);
+    let mut panicking = service_handle(&runtime, true);
+
+    runtime.block_on(async move {
+        let completion = finishing.service_runner().spawn().await_completion();
+        timeout(Duration::from_secs(1), completion)
+            .await
+            .expect("Service to finish on its own")
+            .expect("Service not to panic");
+
+        let completion = panicking.service_runner().spawn().await_completion();
+        let result = timeout(Duration::from_secs(1), completion)
+            .await
+            .expect("Service to finish on its own");
+        assert!(result.expect_err("Service to panic").is_panic());
+    });
+}
@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use overwatch::overwatch::discovery::ServiceDiscovery;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::sync::{Arc, Mutex};
+
+pub struct Database {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for Database {
+    const SERVICE_ID: ServiceId = "Database";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for Database {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    database: ServiceHandle<Database>,
+}
+
+/// Discovery backend recording the calls it gets
+#[derive(Clone, Default)]
+struct MockDiscovery(Arc<Mutex<Vec<String>>>);
+
+impl ServiceDiscovery for MockDiscovery {
+    fn register(&self, service_id: ServiceId) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("register {service_id}"));
+    }
+
+    fn deregister(&self, service_id: ServiceId) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("deregister {service_id}"));
+    }
+}
+
+#[test]
+fn services_are_registered_on_start_and_deregistered_on_stop() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings { database: () };
+    let discovery = MockDiscovery::default();
+    let overwatch = OverwatchRunner::<TestApp>::builder(settings)
+        .service_discovery(discovery.clone())
+        .run()
+        .expect("Services to start");
+    assert_eq!(*discovery.0.lock().unwrap(), ["register Database"]);
+
+    let mut handle = overwatch.handle().clone();
+    overwatch
+        .runtime()
+        .block_on(async move { handle.shutdown().await });
+    overwatch.wait_finished();
+
+    assert_eq!(
+        *discovery.0.lock().unwrap(),
+        ["register Database", "deregister Database"]
+    );
+}
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use overwatch::overwatch::handle::OverwatchHandle;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+pub struct SpanService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for SpanService {
+    const SERVICE_ID: ServiceId = "SpanService";
+    const SERVICE_RELAY_BUFFER_SIZE: usize = 32;
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for SpanService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+type SpanFields = Arc<Mutex<HashMap<String, String>>>;
+
+struct FieldsVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldsVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
+
+/// Captures the fields of the service runner span
+struct RunSpanLayer(SpanFields);
+
+impl<S: Subscriber> Layer<S> for RunSpanLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        if attrs.metadata().name() == "run" {
+            let mut fields = self.0.lock().unwrap();
+            attrs.record(&mut FieldsVisitor(&mut fields));
+        }
+    }
+}
+
+#[test]
+fn run_span_carries_service_metadata() {
+    let runtime = tokio::runtime::Runtime::new().expect("Runtime to be built");
+    let (sender, _receiver) = tokio::sync::mpsc::channel(1);
+    let overwatch_handle = OverwatchHandle::new(runtime.handle().clone(), sender);
+    let mut service_handle = ServiceHandle::<SpanService>::new((), overwatch_handle);
+
+    let fields = SpanFields::default();
+    let subscriber = tracing_subscriber::registry().with(RunSpanLayer(fields.clone()));
+    tracing::subscriber::with_default(subscriber, || {
+        service_handle.service_runner().run();
+    });
+
+    let fields = fields.lock().unwrap();
+    assert_eq!(
+        fields.get("service_id").map(String::as_str),
+        Some("SpanService")
+    );
+    assert_eq!(fields.get("buffer_size").map(String::as_str), Some("32"));
+}
@@ -0,0 +1,221 @@
+use async_trait::async_trait;
+use overwatch::overwatch::handle::OverwatchHandle;
+use overwatch::overwatch::{Error, OverwatchRunner, Services};
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::settings::{ClampLayer, SettingsError, SettingsLayer, ValidationLayer};
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+
+pub struct LenientService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for LenientService {
+    const SERVICE_ID: ServiceId = "LenientService";
+    type Settings = u32;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for LenientService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+pub struct StrictService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for StrictService {
+    const SERVICE_ID: ServiceId = "StrictService";
+    type Settings = u32;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+
+    fn settings_layers() -> Vec<Box<dyn SettingsLayer<Self::Settings>>> {
+        vec![Box::new(ValidationLayer::new(|settings: &u32| {
+            if *settings <= 100 {
+                Ok(())
+            } else {
+                Err(format!("{settings} is over 100"))
+            }
+        }))]
+    }
+}
+
+#[async_trait]
+impl ServiceCore for StrictService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+pub struct ClampedService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for ClampedService {
+    const SERVICE_ID: ServiceId = "ClampedService";
+    type Settings = u32;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+
+    fn settings_layers() -> Vec<Box<dyn SettingsLayer<Self::Settings>>> {
+        vec![Box::new(ClampLayer::new(10, 100))]
+    }
+}
+
+#[async_trait]
+impl ServiceCore for ClampedService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    lenient: ServiceHandle<LenientService>,
+    strict: ServiceHandle<StrictService>,
+}
+
+#[test]
+fn rejected_settings_leave_every_service_untouched() {
+    let runtime = tokio::runtime::Runtime::new().expect("Runtime to be built");
+    let (sender, _receiver) = tokio::sync::mpsc::channel(1);
+    let overwatch_handle = OverwatchHandle::new(runtime.handle().clone(), sender);
+    let mut app = TestApp::new(
+        TestAppServiceSettings {
+            lenient: 1,
+            strict: 1,
+        },
+        overwatch_handle,
+    );
+
+    let result = app.update_settings(TestAppServiceSettings {
+        lenient: 2,
+        strict: 101,
+    });
+    match result {
+        Err(Error::SettingsRejected { failures }) => {
+            assert_eq!(failures.len(), 1);
+            assert_eq!(failures[0].0, "StrictService");
+        }
+        other => panic!("Expected the settings to be rejected, got {other:?}"),
+    }
+    assert_eq!(app.lenient.settings(), 1);
+    assert_eq!(app.strict.settings(), 1);
+
+    app.update_settings(TestAppServiceSettings {
+        lenient: 2,
+        strict: 100,
+    })
+    .expect("Valid settings to be applied");
+    assert_eq!(app.lenient.settings(), 2);
+    assert_eq!(app.strict.settings(), 100);
+}
+
+#[test]
+fn rejected_settings_are_reported_to_the_handle() {
+    let settings = TestAppServiceSettings {
+        lenient: 1,
+        strict: 1,
+    };
+    let overwatch = OverwatchRunner::<TestApp>::run(settings, None);
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let result = handle
+            .update_settings::<TestApp>(TestAppServiceSettings {
+                lenient: 2,
+                strict: 101,
+            })
+            .await;
+        match result {
+            Err(Error::SettingsRejected { failures }) => {
+                assert_eq!(failures.len(), 1);
+                assert_eq!(failures[0].0, "StrictService");
+                assert!(matches!(
+                    &failures[0].1,
+                    SettingsError::Invalid(reason) if reason == "101 is over 100"
+                ));
+            }
+            other => panic!("Expected the settings to be rejected, got {other:?}"),
+        }
+        handle
+            .update_settings::<TestApp>(TestAppServiceSettings {
+                lenient: 2,
+                strict: 100,
+            })
+            .await
+            .expect("Valid settings to be applied");
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
+
+#[derive(Services)]
+struct ClampedApp {
+    clamped: ServiceHandle<ClampedService>,
+}
+
+#[test]
+fn initial_settings_go_through_the_layers() {
+    let runtime = tokio::runtime::Runtime::new().expect("Runtime to be built");
+    let (sender, _receiver) = tokio::sync::mpsc::channel(1);
+    let overwatch_handle = OverwatchHandle::new(runtime.handle().clone(), sender);
+    let app = ClampedApp::new(
+        ClampedAppServiceSettings { clamped: 1000 },
+        overwatch_handle,
+    );
+    assert_eq!(app.clamped.settings(), 100);
+}
+
+#[test]
+fn rejected_initial_settings_fail_startup() {
+    let runtime = tokio::runtime::Runtime::new().expect("Runtime to be built");
+    let (sender, _receiver) = tokio::sync::mpsc::channel(1);
+    let overwatch_handle = OverwatchHandle::new(runtime.handle().clone(), sender);
+    let mut app = TestApp::new(
+        TestAppServiceSettings {
+            lenient: 1,
+            strict: 101,
+        },
+        overwatch_handle,
+    );
+    match app.start_all() {
+        Err(Error::Settings(SettingsError::Invalid(reason))) => {
+            assert_eq!(reason, "101 is over 100");
+        }
+        other => panic!("Expected the initial settings to be rejected, got {other:?}"),
+    }
+
+    // valid settings lift the rejection
+    app.update_settings(TestAppServiceSettings {
+        lenient: 1,
+        strict: 100,
+    })
+    .expect("Valid settings to be applied");
+    let _runtime_context = runtime.enter();
+    app.start("StrictService")
+        .expect("Strict service to start with valid settings");
+}
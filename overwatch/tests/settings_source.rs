@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use futures::StreamExt;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+/// Latest settings each service observed
+#[derive(Default)]
+pub struct ObservedSettings(Mutex<HashMap<ServiceId, String>>);
+
+pub struct NetworkService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for NetworkService {
+    const SERVICE_ID: ServiceId = "NetworkService";
+    type Settings = String;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for NetworkService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let observed = self
+            .state
+            .resource::<ObservedSettings>()
+            .expect("Observed settings to be registered");
+        while let Some(settings) = self.state.settings_reader.next().await {
+            observed
+                .0
+                .lock()
+                .unwrap()
+                .insert(Self::SERVICE_ID, settings);
+        }
+    }
+}
+
+pub struct StorageService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for StorageService {
+    const SERVICE_ID: ServiceId = "StorageService";
+    type Settings = String;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for StorageService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let observed = self
+            .state
+            .resource::<ObservedSettings>()
+            .expect("Observed settings to be registered");
+        while let Some(settings) = self.state.settings_reader.next().await {
+            observed
+                .0
+                .lock()
+                .unwrap()
+                .insert(Self::SERVICE_ID, settings);
+        }
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    network: ServiceHandle<NetworkService>,
+    storage: ServiceHandle<StorageService>,
+}
+
+#[test]
+fn services_observe_settings_pushed_by_source() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings {
+        network: "initial".to_string(),
+        storage: "initial".to_string(),
+    };
+    let overwatch = OverwatchRunner::<TestApp>::builder(settings)
+        .resource(ObservedSettings::default())
+        .run()
+        .expect("Services to start");
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let updates = futures::stream::iter([
+            TestAppServiceSettings {
+                network: "network update".to_string(),
+                storage: "initial".to_string(),
+            },
+            TestAppServiceSettings {
+                network: "network update".to_string(),
+                storage: "storage update".to_string(),
+            },
+        ]);
+        handle
+            .set_settings_source_stream::<TestApp, _>(updates)
+            .await
+            .expect("Settings source to be consumed");
+
+        let observed = handle
+            .resource::<ObservedSettings>()
+            .expect("Observed settings to be registered");
+        let expected = HashMap::from([
+            ("NetworkService", "network update".to_string()),
+            ("StorageService", "storage update".to_string()),
+        ]);
+        timeout(Duration::from_secs(1), async {
+            while *observed.0.lock().unwrap() != expected {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Both services to observe their latest settings");
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
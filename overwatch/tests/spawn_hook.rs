@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::sync::{Arc, Mutex};
+
+pub struct Ingest {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for Ingest {
+    const SERVICE_ID: ServiceId = "Ingest";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for Ingest {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+pub struct Index {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for Index {
+    const SERVICE_ID: ServiceId = "Index";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for Index {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    ingest: ServiceHandle<Ingest>,
+    index: ServiceHandle<Index>,
+}
+
+#[test]
+fn spawn_hook_wraps_every_service() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings {
+        ingest: (),
+        index: (),
+    };
+    let spawned = Arc::new(Mutex::new(Vec::new()));
+    let overwatch = OverwatchRunner::<TestApp>::builder(settings)
+        .spawn_hook({
+            let spawned = spawned.clone();
+            move |service_id, service| {
+                spawned.lock().unwrap().push(service_id);
+                service
+            }
+        })
+        .run()
+        .expect("Services to start");
+    assert_eq!(*spawned.lock().unwrap(), ["Ingest", "Index"]);
+
+    let mut handle = overwatch.handle().clone();
+    overwatch
+        .runtime()
+        .block_on(async move { handle.shutdown().await });
+    overwatch.wait_finished();
+}
@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::RelayMessage;
+use overwatch::services::state::{diff, NoOperator, ServiceState, StateDiff};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch::utils::clock::ManualClock;
+use overwatch_derive::Services;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+pub struct InventoryService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Clone, Debug)]
+pub struct InventoryState {
+    items: Vec<String>,
+}
+
+impl ServiceState for InventoryState {
+    type Settings = ();
+
+    fn from_settings(_settings: &Self::Settings) -> Self {
+        Self {
+            items: vec!["sword".to_string()],
+        }
+    }
+}
+
+impl StateDiff for InventoryState {
+    /// Items added and removed
+    type Diff = (Vec<String>, Vec<String>);
+
+    fn diff(&self, previous: &Self) -> Self::Diff {
+        let added = self
+            .items
+            .iter()
+            .filter(|item| !previous.items.contains(item))
+            .cloned()
+            .collect();
+        let removed = previous
+            .items
+            .iter()
+            .filter(|item| !self.items.contains(item))
+            .cloned()
+            .collect();
+        (added, removed)
+    }
+}
+
+#[derive(Debug)]
+pub struct Swap {
+    item: String,
+    done: oneshot::Sender<()>,
+}
+
+impl RelayMessage for Swap {}
+
+impl ServiceData for InventoryService {
+    const SERVICE_ID: ServiceId = "InventoryService";
+    type Settings = ();
+    type State = InventoryState;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = Swap;
+}
+
+#[async_trait]
+impl ServiceCore for InventoryService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while let Some(Swap { item, done }) = self.state.inbound_relay.recv().await {
+            self.state
+                .state_updater
+                .update(InventoryState { items: vec![item] });
+            let _ = done.send(());
+        }
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    inventory: ServiceHandle<InventoryService>,
+}
+
+#[test]
+fn snapshots_diff_shows_state_mutation() {
+    let clock = ManualClock::new();
+    let settings: TestAppServiceSettings = TestAppServiceSettings { inventory: () };
+    let overwatch = OverwatchRunner::<TestApp>::builder(settings)
+        .clock(clock.clone())
+        .run()
+        .expect("Services to start");
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        let before = handle
+            .state_snapshot::<InventoryService>()
+            .await
+            .expect("A snapshot of the inventory state");
+
+        let relay = handle
+            .relay::<InventoryService>()
+            .connect()
+            .await
+            .expect("Inventory service to be running");
+        let (done, swapped) = oneshot::channel();
+        relay
+            .send(Swap {
+                item: "shield".to_string(),
+                done,
+            })
+            .await
+            .expect("Swap to be sent");
+        swapped.await.expect("Inventory to be updated");
+        clock.advance(Duration::from_secs(60));
+
+        let after = handle
+            .state_snapshot::<InventoryService>()
+            .await
+            .expect("A snapshot of the inventory state");
+        assert_eq!(
+            after.taken_at.duration_since(before.taken_at).unwrap(),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            diff(&before, &after),
+            (vec!["shield".to_string()], vec!["sword".to_string()])
+        );
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
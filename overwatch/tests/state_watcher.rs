@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use overwatch::overwatch::handle::OverwatchHandle;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, ServiceState, StateOperator};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+pub struct CounterService {
+    state: ServiceStateHandle<Self>,
+}
+
+#[derive(Clone)]
+pub struct CounterState {
+    value: usize,
+}
+
+impl ServiceState for CounterState {
+    type Settings = ();
+
+    fn from_settings(_settings: &Self::Settings) -> Self {
+        Self { value: 0 }
+    }
+}
+
+impl ServiceData for CounterService {
+    const SERVICE_ID: ServiceId = "CounterService";
+    type Settings = ();
+    type State = CounterState;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for CounterService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        self.state.state_updater.update(CounterState { value: 7 });
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    counter_service: ServiceHandle<CounterService>,
+}
+
+#[test]
+fn late_watcher_receives_current_state() {
+    let settings: TestAppServiceSettings = TestAppServiceSettings {
+        counter_service: (),
+    };
+    let overwatch = OverwatchRunner::<TestApp>::run(settings, None);
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        sleep(Duration::from_millis(100)).await;
+        let watcher = handle
+            .state_watcher::<CounterService>()
+            .await
+            .expect("A watcher for the counter service state");
+        assert_eq!(watcher.state_cloned().value, 7);
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}
+
+/// Set once the [`TrackedOperator`] state task ends and drops it
+static OPERATOR_DROPPED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone)]
+pub struct TrackedOperator;
+
+#[async_trait]
+impl StateOperator for TrackedOperator {
+    type StateInput = CounterState;
+
+    fn from_settings<Settings>(_settings: Settings) -> Self {
+        Self
+    }
+
+    async fn run(&mut self, _state: Self::StateInput) {}
+}
+
+impl Drop for TrackedOperator {
+    fn drop(&mut self) {
+        OPERATOR_DROPPED.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Service that updates its state once and exits
+pub struct OneShotService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for OneShotService {
+    const SERVICE_ID: ServiceId = "OneShotService";
+    type Settings = ();
+    type State = CounterState;
+    type StateOperator = TrackedOperator;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for OneShotService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        self.state.state_updater.update(CounterState { value: 1 });
+    }
+}
+
+#[test]
+fn state_task_ends_along_with_the_service() {
+    let runtime = tokio::runtime::Runtime::new().expect("Runtime to be built");
+    let (sender, _receiver) = tokio::sync::mpsc::channel(1);
+    let overwatch_handle = OverwatchHandle::new(runtime.handle().clone(), sender);
+    let mut service_handle = ServiceHandle::<OneShotService>::new((), overwatch_handle);
+    let runner = service_handle.service_runner().spawn();
+
+    runtime.block_on(async {
+        runner
+            .await_completion()
+            .await
+            .expect("Service to finish without panicking");
+        timeout(Duration::from_secs(1), async {
+            while !OPERATOR_DROPPED.load(Ordering::SeqCst) {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("State task to end once the service finished");
+    });
+    // the watcher held by the handle keeps the latest state
+    assert_eq!(service_handle.state_watcher().state_cloned().value, 1);
+}
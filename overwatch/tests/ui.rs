@@ -0,0 +1,6 @@
+#[test]
+fn relays_to_targets() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/relays_to_ok.rs");
+    cases.compile_fail("tests/ui/relays_to_missing.rs");
+}
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+
+pub struct PingService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for PingService {
+    const SERVICE_ID: ServiceId = "PingService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for PingService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+#[derive(Services)]
+struct App {
+    #[overwatch(relays_to(PongService))]
+    ping: ServiceHandle<PingService>,
+}
+
+fn main() {}
@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+
+mod pong {
+    use super::*;
+
+    pub struct PongService {
+        state: ServiceStateHandle<Self>,
+    }
+
+    impl ServiceData for PongService {
+        const SERVICE_ID: ServiceId = "PongService";
+        type Settings = ();
+        type State = NoState<Self::Settings>;
+        type StateOperator = NoOperator<Self::State>;
+        type Message = NoMessage;
+    }
+
+    #[async_trait]
+    impl ServiceCore for PongService {
+        fn init(state: ServiceStateHandle<Self>) -> Self {
+            Self { state }
+        }
+
+        async fn run(mut self) {
+            while self.state.inbound_relay.recv().await.is_some() {}
+        }
+    }
+}
+
+use pong::PongService;
+
+pub struct PingService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for PingService {
+    const SERVICE_ID: ServiceId = "PingService";
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for PingService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+#[derive(Services)]
+struct App {
+    // the target is spelled with its module, the field with the imported name
+    #[overwatch(relays_to(pong::PongService))]
+    ping: ServiceHandle<PingService>,
+    pong: ServiceHandle<PongService>,
+}
+
+fn main() {
+    let _accessor = App::relay_ping_to_pong;
+}
@@ -0,0 +1,124 @@
+use async_trait::async_trait;
+use overwatch::overwatch::OverwatchRunner;
+use overwatch::services::handle::{ServiceHandle, ServiceStateHandle};
+use overwatch::services::relay::NoMessage;
+use overwatch::services::state::{NoOperator, NoState};
+use overwatch::services::{ServiceCore, ServiceData, ServiceId};
+use overwatch_derive::Services;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+const POOL_CONCURRENCY: usize = 2;
+const TASKS_PER_SERVICE: usize = 4;
+
+/// Tracks the pool tasks running at once
+#[derive(Debug, Default)]
+pub struct PoolTracker {
+    running: AtomicUsize,
+    max_running: AtomicUsize,
+    finished: AtomicUsize,
+}
+
+async fn tracked_task(tracker: Arc<PoolTracker>) {
+    let running = tracker.running.fetch_add(1, Ordering::SeqCst) + 1;
+    tracker.max_running.fetch_max(running, Ordering::SeqCst);
+    sleep(Duration::from_millis(20)).await;
+    tracker.running.fetch_sub(1, Ordering::SeqCst);
+    tracker.finished.fetch_add(1, Ordering::SeqCst);
+}
+
+pub struct IndexerService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for IndexerService {
+    const SERVICE_ID: ServiceId = "IndexerService";
+    type Settings = Arc<PoolTracker>;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for IndexerService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let tracker = self.state.settings_reader.get_updated_settings();
+        for _ in 0..TASKS_PER_SERVICE {
+            self.state
+                .pool_spawn(tracked_task(tracker.clone()))
+                .expect("Worker pool to be configured");
+        }
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+pub struct ThumbnailService {
+    state: ServiceStateHandle<Self>,
+}
+
+impl ServiceData for ThumbnailService {
+    const SERVICE_ID: ServiceId = "ThumbnailService";
+    type Settings = Arc<PoolTracker>;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = NoMessage;
+}
+
+#[async_trait]
+impl ServiceCore for ThumbnailService {
+    fn init(state: ServiceStateHandle<Self>) -> Self {
+        Self { state }
+    }
+
+    async fn run(mut self) {
+        let tracker = self.state.settings_reader.get_updated_settings();
+        for _ in 0..TASKS_PER_SERVICE {
+            self.state
+                .pool_spawn(tracked_task(tracker.clone()))
+                .expect("Worker pool to be configured");
+        }
+        while self.state.inbound_relay.recv().await.is_some() {}
+    }
+}
+
+#[derive(Services)]
+struct TestApp {
+    indexer: ServiceHandle<IndexerService>,
+    thumbnailer: ServiceHandle<ThumbnailService>,
+}
+
+#[test]
+fn pool_tasks_respect_global_cap() {
+    let tracker = Arc::new(PoolTracker::default());
+    let settings: TestAppServiceSettings = TestAppServiceSettings {
+        indexer: tracker.clone(),
+        thumbnailer: tracker.clone(),
+    };
+    let overwatch = OverwatchRunner::<TestApp>::builder(settings)
+        .worker_pool(POOL_CONCURRENCY)
+        .run()
+        .expect("Services to start");
+    let mut handle = overwatch.handle().clone();
+
+    overwatch.runtime().block_on(async move {
+        timeout(Duration::from_secs(2), async {
+            while tracker.finished.load(Ordering::SeqCst) < 2 * TASKS_PER_SERVICE {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Every pool task to finish");
+        let max_running = tracker.max_running.load(Ordering::SeqCst);
+        assert!(max_running > 0);
+        assert!(max_running <= POOL_CONCURRENCY);
+        handle.shutdown().await;
+    });
+
+    overwatch.wait_finished();
+}